@@ -1,9 +1,17 @@
-use crate::frame::Frame;
+use crate::clock::Time;
+use crate::frame::{Frame, Size};
+use crate::input::Event;
+use crate::parameters::{self, Parameter, ParameterError, Value};
+use std::fmt;
 
+mod brians_brain;
+mod cyclic;
 mod drops;
+mod elementary;
 mod gol;
 mod hexagons;
 mod hills;
+mod langtons_ant;
 mod mandelbrot;
 mod matrix;
 mod moving_blocks;
@@ -13,10 +21,17 @@ mod qrcode;
 mod rainbow;
 mod random_walkers;
 mod text_overlay;
+mod ticker;
+mod triangles;
+mod wireworld;
+pub use brians_brain::BriansBrain;
+pub use cyclic::Cyclic;
 pub use drops::Drops;
+pub use elementary::Elementary;
 pub use gol::GOL;
 pub use hexagons::Hexagons;
 pub use hills::Hills;
+pub use langtons_ant::LangtonsAnt;
 pub use mandelbrot::Mandelbrot;
 pub use matrix::Matrix;
 pub use moving_blocks::MovingBlocks;
@@ -25,6 +40,9 @@ pub use qrcode::QrCode;
 pub use rainbow::Rainbow;
 pub use random_walkers::RandomWalkers;
 pub use text_overlay::TextOverlay;
+pub use ticker::Ticker;
+pub use triangles::Triangles;
+pub use wireworld::Wireworld;
 
 pub trait Animation {
     /// returns the name of the animation
@@ -33,6 +51,283 @@ pub trait Animation {
     /// returns the author of the animation
     fn author(&self) -> &'static str;
 
+    /// called once with the size of the frames before the first call of `render()`.
+    fn init(&mut self, _size: Size) {}
+
+    /// called before `render()` when the size of the frames changed since the last call.
+    ///
+    /// By default the animation is initialized again with the new size.
+    fn resize(&mut self, _old: Size, new: Size) {
+        self.init(new);
+    }
+
+    /// called once after the last frame, when the animation is taken off the screen.
+    fn finish(&mut self) {}
+
     /// writes the next animation step into the given frame.
-    fn render(&mut self, frame: &mut Frame);
+    ///
+    /// Movement should be based on the given time, not on the number of calls.
+    fn render(&mut self, frame: &mut Frame, time: &Time);
+
+    /// whether the animation has nothing more to show and would like to end before its time is up.
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// reacts to input, mouse events are given in cells of the last rendered frame.
+    ///
+    /// Only called after `init()`. Keys that are used by the binary are not passed on.
+    fn handle_event(&mut self, _event: &Event) {}
+
+    /// describes the parameters of the animation and their current values.
+    fn parameters(&self) -> Vec<Parameter> {
+        Vec::new()
+    }
+
+    /// reseeds the random number generator of the animation.
+    ///
+    /// Two instances with the same seed and parameters render the same frames for the same times.
+    fn seed(&mut self, _seed: u64) {}
+
+    /// sets a parameter.
+    ///
+    /// The value is already checked against the kind and range given by `parameters()`.
+    fn set_parameter(&mut self, _name: &str, _value: Value) {}
+
+    /// parses the text for the named parameter and sets it.
+    fn set_parameter_from_str(&mut self, name: &str, text: &str) -> Result<(), ParameterError> {
+        let value = parameters::parse(&self.parameters(), name, text)?;
+        self.set_parameter(name, value);
+        Ok(())
+    }
+}
+
+/// An animation that can be looked up by name.
+pub struct Entry {
+    /// a short name to select the animation on the command line.
+    pub id: &'static str,
+    /// the name of the animation, the same as `Animation::name()`
+    pub name: &'static str,
+    /// the author of the animation, the same as `Animation::author()`
+    pub author: &'static str,
+    /// whether the animation is part of the default shuffle rotation.
+    pub in_rotation: bool,
+    /// creates a new instance of the animation.
+    pub create: fn() -> Box<dyn Animation>,
+}
+
+impl Entry {
+    /// creates a new instance of the animation with a seeded random number generator.
+    pub fn create_seeded(&self, seed: u64) -> Box<dyn Animation> {
+        let mut animation = (self.create)();
+        animation.seed(seed);
+        animation
+    }
+
+    /// checks if the given name refers to this animation, either by its id or by its name.
+    pub fn matches(&self, name: &str) -> bool {
+        self.id.eq_ignore_ascii_case(name) || self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Entry({})", self.id)
+    }
+}
+
+/// Every animation that can be shown on its own.
+///
+/// When you add a new animation, add it here as well.
+pub const REGISTRY: &[Entry] = &[
+    Entry {
+        id: "hexagons",
+        name: Hexagons::NAME,
+        author: Hexagons::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Hexagons>::default(),
+    },
+    Entry {
+        id: "drops",
+        name: Drops::NAME,
+        author: Drops::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Drops>::default(),
+    },
+    Entry {
+        id: "hills",
+        name: Hills::NAME,
+        author: Hills::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Hills>::default(),
+    },
+    Entry {
+        id: "moving-blocks",
+        name: MovingBlocks::NAME,
+        author: MovingBlocks::AUTHOR,
+        in_rotation: true,
+        create: || Box::<MovingBlocks>::default(),
+    },
+    Entry {
+        id: "rainbow",
+        name: Rainbow::NAME,
+        author: Rainbow::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Rainbow>::default(),
+    },
+    Entry {
+        id: "gol",
+        name: GOL::NAME,
+        author: GOL::AUTHOR,
+        in_rotation: true,
+        create: || Box::<GOL>::default(),
+    },
+    Entry {
+        id: "brians-brain",
+        name: BriansBrain::NAME,
+        author: BriansBrain::AUTHOR,
+        in_rotation: true,
+        create: || Box::<BriansBrain>::default(),
+    },
+    Entry {
+        id: "wireworld",
+        name: Wireworld::NAME,
+        author: Wireworld::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Wireworld>::default(),
+    },
+    Entry {
+        id: "langtons-ant",
+        name: LangtonsAnt::NAME,
+        author: LangtonsAnt::AUTHOR,
+        in_rotation: true,
+        create: || Box::<LangtonsAnt>::default(),
+    },
+    Entry {
+        id: "elementary",
+        name: Elementary::NAME,
+        author: Elementary::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Elementary>::default(),
+    },
+    Entry {
+        id: "cyclic",
+        name: Cyclic::NAME,
+        author: Cyclic::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Cyclic>::default(),
+    },
+    Entry {
+        id: "qrcode",
+        name: QrCode::NAME,
+        author: QrCode::AUTHOR,
+        in_rotation: true,
+        create: || Box::new(QrCode::new("https://github.com/991jo/asciimation", (5, 6))),
+    },
+    Entry {
+        id: "matrix",
+        name: Matrix::NAME,
+        author: Matrix::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Matrix>::default(),
+    },
+    Entry {
+        id: "mandelbrot",
+        name: Mandelbrot::NAME,
+        author: Mandelbrot::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Mandelbrot>::default(),
+    },
+    Entry {
+        id: "triangles",
+        name: Triangles::NAME,
+        author: Triangles::AUTHOR,
+        in_rotation: true,
+        create: || Box::<Triangles>::default(),
+    },
+    Entry {
+        id: "random-walkers",
+        name: RandomWalkers::NAME,
+        author: RandomWalkers::AUTHOR,
+        in_rotation: false,
+        create: || Box::<RandomWalkers>::default(),
+    },
+];
+
+/// Looks up an animation by its id or name, ignoring the case.
+pub fn find(name: &str) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.matches(name))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::runner::Runner;
+    use std::time::Duration;
+
+    /// renders a few frames and returns the last one as text with colors.
+    fn render(entry: &Entry, seed: u64) -> String {
+        let mut runner = Runner::new(entry.create_seeded(seed));
+        let mut frame = Frame::new(0, 0);
+
+        for index in 0..10 {
+            (frame, _) = runner.render(Size::new(120, 50), Duration::from_millis(50) * index);
+        }
+
+        frame.data.iter().map(|cell| cell.render()).collect()
+    }
+
+    #[test]
+    fn test_resize() {
+        // shrinking and growing the frame must not panic
+        for entry in REGISTRY.iter() {
+            let mut runner = Runner::new(entry.create_seeded(1));
+
+            for (index, size) in [(120, 50), (40, 12), (1, 1), (130, 60), (50, 30)]
+                .into_iter()
+                .enumerate()
+            {
+                let (frame, _) = runner.render(
+                    Size::new(size.0, size.1),
+                    Duration::from_millis(50) * index as u32,
+                );
+                assert_eq!(frame.size(), Size::new(size.0, size.1), "{}", entry.id);
+            }
+            runner.finish();
+        }
+    }
+
+    #[test]
+    fn test_mouse() {
+        use crate::input::{Button, Mouse, MouseKind};
+
+        // clicks inside and outside of the frame must not panic
+        for entry in REGISTRY.iter() {
+            let mut runner = Runner::new(entry.create_seeded(1));
+            runner.render(Size::new(40, 12), Duration::ZERO);
+
+            for (kind, x, y) in [
+                (MouseKind::Press(Button::Left), 0, 0),
+                (MouseKind::Drag(Button::Left), 39, 11),
+                (MouseKind::ScrollUp, 20, 6),
+                (MouseKind::Press(Button::Left), 40, 12),
+                (MouseKind::Release, 100, 100),
+            ] {
+                runner.handle_event(&Event::Mouse(Mouse { kind, x, y }));
+            }
+            runner.render(Size::new(40, 12), Duration::from_millis(50));
+        }
+    }
+
+    #[test]
+    fn test_seed() {
+        for entry in REGISTRY.iter() {
+            assert_eq!(render(entry, 42), render(entry, 42), "{}", entry.id);
+        }
+
+        assert_ne!(
+            render(find("gol").unwrap(), 1),
+            render(find("gol").unwrap(), 2)
+        );
+    }
 }
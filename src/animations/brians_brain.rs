@@ -0,0 +1,177 @@
+use super::prelude::*;
+use crate::automaton::{Generations, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Off,
+    On,
+    /// on in the last generation, can not be switched on in the next one
+    Dying,
+}
+
+/// Brian's Brain, a cell switches on with exactly two neighbors that are on and dies right after.
+pub struct BriansBrain {
+    cells: Grid<Cell>,
+    generations: Generations,
+    /// share of cells that are on at the start
+    density: f32,
+    rng: StdRng,
+}
+
+impl BriansBrain {
+    pub const NAME: &'static str = "Brian's Brain";
+    pub const AUTHOR: &'static str = "Jo";
+
+    fn step(&mut self) {
+        self.cells.step(|cells, x, y| match cells.get(x, y) {
+            Cell::Off if cells.count(x, y, Cell::On) == 2 => Cell::On,
+            Cell::Off => Cell::Off,
+            Cell::On => Cell::Dying,
+            Cell::Dying => Cell::Off,
+        });
+    }
+
+    fn initialize(&mut self) {
+        let (rng, density) = (&mut self.rng, self.density);
+        self.cells.fill_with(|_, _| {
+            if rng.gen::<f32>() < density {
+                Cell::On
+            } else {
+                Cell::Off
+            }
+        });
+    }
+
+    fn draw(cell: Cell) -> Option<Character> {
+        let (character, color) = match cell {
+            Cell::Off => return None,
+            Cell::On => ('#', Color::white()),
+            Cell::Dying => (
+                '+',
+                Color {
+                    r: 0.2,
+                    g: 0.4,
+                    b: 1.0,
+                },
+            ),
+        };
+
+        Some(Character {
+            character,
+            color,
+            background: None,
+        })
+    }
+}
+
+impl Default for BriansBrain {
+    fn default() -> BriansBrain {
+        BriansBrain {
+            cells: Grid::new(Size::new(0, 0), Cell::Off, true),
+            generations: Generations::new(10.0),
+            density: 0.2,
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Animation for BriansBrain {
+    fn name(&self) -> &'static str {
+        BriansBrain::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        BriansBrain::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "generations per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 120.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "density",
+                description: "share of cells that are on at the start",
+                kind: Kind::Float { min: 0.0, max: 1.0 },
+                value: Value::Float(self.density as f64),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "density" => self.density = value.as_float().unwrap() as f32,
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, Cell::Off, true);
+        self.initialize();
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, y }) = *event else {
+            return;
+        };
+        let size = self.cells.size();
+        if x >= size.width || y >= size.height {
+            return;
+        }
+
+        if let MouseKind::Press(Button::Left) | MouseKind::Drag(Button::Left) = kind {
+            self.cells.set(x, y, Cell::On);
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            let previous = self.cells.clone();
+            self.step();
+            // everything switched off, start again
+            if self.cells == previous {
+                self.initialize();
+            }
+        }
+
+        self.cells.draw(frame, BriansBrain::draw);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut brain = BriansBrain {
+            cells: Grid::new(Size::new(6, 6), Cell::Off, false),
+            ..BriansBrain::default()
+        };
+        brain.cells.set(2, 2, Cell::On);
+        brain.cells.set(3, 2, Cell::On);
+
+        brain.step();
+        assert_eq!(brain.cells.get(2, 2), Cell::Dying);
+        // the cells above and below both pairs switch on
+        for x in 2..4 {
+            assert_eq!(brain.cells.get(x, 1), Cell::On);
+            assert_eq!(brain.cells.get(x, 3), Cell::On);
+        }
+        assert_eq!(brain.cells.get(1, 2), Cell::Off);
+
+        brain.step();
+        assert_eq!(brain.cells.get(2, 2), Cell::Off);
+    }
+}
@@ -0,0 +1,159 @@
+use super::prelude::*;
+use crate::automaton::{Generations, Grid};
+
+/// A cyclic cellular automaton, a cell moves on to the next state when enough of its neighbors
+/// are in it, which turns random noise into spirals.
+pub struct Cyclic {
+    cells: Grid<u8>,
+    /// the number of states, the one after the last is the first again
+    states: u8,
+    /// the number of neighbors in the next state needed to move on
+    threshold: usize,
+    /// the hue of the first state
+    hue: f32,
+    generations: Generations,
+    rng: StdRng,
+}
+
+impl Cyclic {
+    pub const NAME: &'static str = "Cyclic Automaton";
+    pub const AUTHOR: &'static str = "Jo";
+
+    fn step(&mut self) {
+        let (states, threshold) = (self.states, self.threshold);
+
+        self.cells.step(|cells, x, y| {
+            let next = (cells.get(x, y) + 1) % states;
+            let count = cells
+                .orthogonal_neighbors(x, y)
+                .filter(|&state| state == next)
+                .count();
+            if count >= threshold {
+                next
+            } else {
+                cells.get(x, y)
+            }
+        });
+    }
+
+    fn initialize(&mut self) {
+        self.hue = self.rng.gen();
+        let (rng, states) = (&mut self.rng, self.states);
+        self.cells.fill_with(|_, _| rng.gen_range(0..states));
+    }
+}
+
+impl Default for Cyclic {
+    fn default() -> Cyclic {
+        Cyclic {
+            cells: Grid::new(Size::new(0, 0), 0, true),
+            states: 14,
+            threshold: 1,
+            hue: 0.0,
+            generations: Generations::new(15.0),
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Animation for Cyclic {
+    fn name(&self) -> &'static str {
+        Cyclic::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        Cyclic::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "generations per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 120.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "states",
+                description: "states that every cell cycles through",
+                kind: Kind::Integer { min: 2, max: 32 },
+                value: Value::Integer(self.states as i64),
+            },
+            Parameter {
+                name: "threshold",
+                description: "neighbors in the next state needed to move on",
+                kind: Kind::Integer { min: 1, max: 4 },
+                value: Value::Integer(self.threshold as i64),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "states" => self.states = value.as_integer().unwrap() as u8,
+            "threshold" => self.threshold = value.as_integer().unwrap() as usize,
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, 0, true);
+        self.initialize();
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            let previous = self.cells.clone();
+            self.step();
+            // nothing moves anymore, start again
+            if self.cells == previous {
+                self.initialize();
+            }
+        }
+
+        self.cells.draw(frame, |state| {
+            Some(Character {
+                character: '█',
+                color: HSVColor {
+                    h: (self.hue + state as f32 / self.states as f32).fract(),
+                    s: 0.8,
+                    v: 0.9,
+                }
+                .into(),
+                background: None,
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut cyclic = Cyclic {
+            states: 3,
+            ..Cyclic::default()
+        };
+        cyclic.cells = Grid::new(Size::new(3, 1), 0, false);
+        cyclic.cells.set(1, 0, 1);
+        cyclic.cells.set(2, 0, 2);
+
+        // every cell is eaten by a neighbor in its next state
+        cyclic.step();
+        assert_eq!(cyclic.cells.cells(), [1, 2, 2]);
+
+        cyclic.threshold = 2;
+        cyclic.step();
+        assert_eq!(cyclic.cells.cells(), [1, 2, 2]);
+    }
+}
@@ -2,65 +2,163 @@ use super::prelude::*;
 use crate::frame::HSVColor;
 use crate::utils::sample;
 use nalgebra::base::Vector2;
-use rand::Rng;
 
-#[derive(Default)]
+/// clicked drops beyond this number replace the oldest ones
+const MAX_DROPS: usize = 20;
+
+struct Ripple {
+    center: Vector2<f32>,
+    /// the time the drop fell
+    start: f32,
+}
+
 pub struct Drops {
-    step: usize,
-    initialized: bool,
-    centers: Vec<Vector2<f32>>,
+    /// seconds since the start
+    time: f32,
+    count: usize,
+    ripples: Vec<Ripple>,
+    size: Size,
+    resolution: Resolution,
+    rng: StdRng,
+}
+
+impl Default for Drops {
+    fn default() -> Self {
+        Drops {
+            time: 0.0,
+            count: 3,
+            ripples: Vec::new(),
+            size: Size::new(0, 0),
+            resolution: Resolution::Text,
+            rng: StdRng::from_entropy(),
+        }
+    }
 }
 
 impl Drops {
-    fn initialize(&mut self, _frame: &mut Frame, ratio: f32) {
-        let mut rng = rand::thread_rng();
+    pub const NAME: &'static str = "Drops";
+    pub const AUTHOR: &'static str = "Jo";
+
+    /// the width of the sampled area, its height is 1
+    fn ratio(size: Size) -> f32 {
+        size.width as f32 / size.height as f32 / 2.0
+    }
 
-        for _ in 0..3 {
-            self.centers
-                .push(Vector2::new(rng.gen::<f32>() * ratio, rng.gen()));
+    fn initialize(&mut self, ratio: f32) {
+        self.ripples.clear();
+        for _ in 0..self.count {
+            self.ripples.push(Ripple {
+                center: Vector2::new(self.rng.gen::<f32>() * ratio, self.rng.gen()),
+                start: 0.0,
+            });
         }
     }
 }
 
 impl Animation for Drops {
     fn name(&self) -> &'static str {
-        "Drops"
+        Drops::NAME
     }
 
     fn author(&self) -> &'static str {
-        "Jo"
+        Drops::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "count",
+                description: "number of drops",
+                kind: Kind::Integer { min: 1, max: 20 },
+                value: Value::Integer(self.count as i64),
+            },
+            Parameter {
+                name: "resolution",
+                description: "pixels per character",
+                kind: Kind::Choice(Resolution::NAMES),
+                value: Value::Text(self.resolution.name().to_string()),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "count" => self.count = value.as_integer().unwrap() as usize,
+            "resolution" => self.resolution = value.as_text().unwrap().parse().unwrap(),
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.size = size;
+        self.initialize(Drops::ratio(size));
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        // calculate a frame height
-        let ratio = frame.x as f32 / frame.y as f32 / 2.0;
+    fn resize(&mut self, old: Size, new: Size) {
+        // keep the drops at the same relative position
+        let scale = Drops::ratio(new) / Drops::ratio(old);
+        for ripple in self.ripples.iter_mut() {
+            ripple.center.x *= scale;
+        }
+        self.size = new;
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        // a click lets a new drop fall at the cell
+        let Event::Mouse(Mouse {
+            kind: MouseKind::Press(Button::Left),
+            x,
+            y,
+        }) = *event
+        else {
+            return;
+        };
+        if x >= self.size.width || y >= self.size.height {
+            return;
+        }
 
-        if !self.initialized {
-            self.initialized = true;
-            self.initialize(frame, ratio);
+        if self.ripples.len() >= MAX_DROPS {
+            self.ripples.remove(0);
         }
+        self.ripples.push(Ripple {
+            center: Vector2::new(
+                x as f32 / self.size.width as f32 * Drops::ratio(self.size),
+                y as f32 / self.size.height as f32,
+            ),
+            start: self.time,
+        });
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        let ratio = Drops::ratio(frame.size());
 
+        let mut canvas = Canvas::new(frame.size(), self.resolution);
         sample(
-            frame,
+            &mut canvas,
             0.0,
             ratio,
             0.0,
             1.0,
-            self.step as f32 / 60.0,
-            &|x, y, time| drop(&self.centers, x, y, time),
+            self.time,
+            &|x, y, time| drop(&self.ripples, x, y, time),
         );
-        self.step += 1;
+        canvas.draw(frame);
+        self.time += time.delta;
     }
 }
 
-fn drop(centers: &[Vector2<f32>], x: f32, y: f32, time: f32) -> Character {
+fn drop(ripples: &[Ripple], x: f32, y: f32, time: f32) -> Character {
     let pos = Vector2::new(x, y);
-    let grown_distance = time * 0.1;
 
     let mut height: f32 = 0.0;
 
-    for center in centers.iter() {
-        let distance = center.metric_distance(&pos);
+    for ripple in ripples.iter() {
+        let grown_distance = (time - ripple.start) * 0.1;
+        let distance = ripple.center.metric_distance(&pos);
         if distance > grown_distance {
             continue;
         } else {
@@ -82,5 +180,6 @@ fn drop(centers: &[Vector2<f32>], x: f32, y: f32, time: f32) -> Character {
     Character {
         color: color.into(),
         character: '@',
+        background: None,
     }
 }
@@ -0,0 +1,201 @@
+use super::prelude::*;
+use crate::automaton::{Generations, Grid};
+
+/// Elementary cellular automata like rule 30 and 110, one row per generation scrolling upwards.
+pub struct Elementary {
+    /// 0 for dead cells, otherwise the generation in which the cell was alive
+    cells: Grid<u32>,
+    /// the Wolfram code, bit `n` is the next state for the neighborhood `n` (left, cell, right)
+    rule: u8,
+    /// whether the first row has a single living cell in the middle, or random ones
+    single: bool,
+    generation: u32,
+    /// the hue of the first generation, the hue changes slowly with every generation
+    hue: f32,
+    generations: Generations,
+    rng: StdRng,
+}
+
+impl Elementary {
+    pub const NAME: &'static str = "Elementary Automaton";
+    pub const AUTHOR: &'static str = "Jo";
+
+    const STARTS: &'static [&'static str] = &["single", "random"];
+
+    /// moves every row up and computes a new bottom row from the last one.
+    fn step(&mut self) {
+        self.generation += 1;
+        let (rule, generation) = (self.rule, self.generation);
+
+        self.cells.step(|cells, x, y| {
+            if y + 1 < cells.size().height {
+                return cells.get(x, y + 1);
+            }
+
+            let alive = |dx| {
+                cells
+                    .offset(x, y, dx, 0)
+                    .is_some_and(|(x, y)| cells.get(x, y) != 0)
+            };
+            let neighborhood = (alive(-1) as u8) << 2 | (alive(0) as u8) << 1 | alive(1) as u8;
+            if rule >> neighborhood & 1 == 1 {
+                generation
+            } else {
+                0
+            }
+        });
+    }
+
+    fn initialize(&mut self) {
+        self.cells.fill(0);
+        self.generation = 1;
+        self.hue = self.rng.gen();
+
+        let size = self.cells.size();
+        if size.height == 0 {
+            return;
+        }
+        let y = size.height - 1;
+        if self.single {
+            self.cells.set(size.width / 2, y, 1);
+        } else {
+            for x in 0..size.width {
+                let alive = self.rng.gen_bool(0.5);
+                self.cells.set(x, y, alive as u32);
+            }
+        }
+    }
+}
+
+impl Default for Elementary {
+    fn default() -> Elementary {
+        Elementary {
+            cells: Grid::new(Size::new(0, 0), 0, true),
+            rule: 30,
+            single: true,
+            generation: 1,
+            hue: 0.0,
+            generations: Generations::new(12.0),
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Animation for Elementary {
+    fn name(&self) -> &'static str {
+        Elementary::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        Elementary::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "generations per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 120.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "rule",
+                description: "Wolfram code of the rule, like 30, 90 or 110",
+                kind: Kind::Integer { min: 0, max: 255 },
+                value: Value::Integer(self.rule as i64),
+            },
+            Parameter {
+                name: "start",
+                description: "a single living cell in the middle or random cells",
+                kind: Kind::Choice(Elementary::STARTS),
+                value: Value::Text(Elementary::STARTS[!self.single as usize].to_string()),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "rule" => self.rule = value.as_integer().unwrap() as u8,
+            "start" => self.single = value.as_text().unwrap() == "single",
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, 0, true);
+        self.initialize();
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, .. }) = *event else {
+            return;
+        };
+        let size = self.cells.size();
+        if x >= size.width || size.height == 0 {
+            return;
+        }
+
+        // a click toggles the cell in the newest row
+        if kind == MouseKind::Press(Button::Left) {
+            let y = size.height - 1;
+            let cell = if self.cells.get(x, y) == 0 {
+                self.generation
+            } else {
+                0
+            };
+            self.cells.set(x, y, cell);
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            self.step();
+        }
+
+        self.cells.draw(frame, |generation| {
+            (generation != 0).then(|| Character {
+                character: '█',
+                color: HSVColor {
+                    h: (self.hue + generation as f32 * 0.004).fract(),
+                    s: 0.7,
+                    v: 1.0,
+                }
+                .into(),
+                background: None,
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rule_30() {
+        let mut elementary = Elementary::default();
+        elementary.init(Size::new(7, 3));
+        elementary.step();
+        elementary.step();
+
+        let rows: Vec<String> = (0..3)
+            .map(|y| {
+                (0..7)
+                    .map(|x| match elementary.cells.get(x, y) {
+                        0 => '.',
+                        _ => '#',
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, ["...#...", "..###..", ".##..#."]);
+    }
+}
@@ -1,76 +1,289 @@
 use super::prelude::*;
-use rand::Rng;
+use crate::automaton::{Generations, Grid};
+use crate::frame::Gradient;
+use crate::life::{Pattern, Rule};
+use crate::parameters::{self, ParameterError};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+/// What happens when the cells died out or only repeat themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stagnation {
+    /// start again with random cells or the pattern
+    Reseed,
+    /// drop a patch of random cells somewhere
+    Inject,
+    /// ask to end the animation early
+    End,
+    /// keep showing it
+    Ignore,
+}
+
+impl Stagnation {
+    const NAMES: &'static [&'static str] = &["reseed", "inject", "end", "ignore"];
+    const ALL: [Stagnation; 4] = [
+        Stagnation::Reseed,
+        Stagnation::Inject,
+        Stagnation::End,
+        Stagnation::Ignore,
+    ];
+
+    fn name(&self) -> &'static str {
+        Stagnation::NAMES[*self as usize]
+    }
+
+    fn from_name(name: &str) -> Option<Stagnation> {
+        Stagnation::ALL
+            .into_iter()
+            .find(|stagnation| stagnation.name() == name)
+    }
+}
+
+/// What the color of a living cell shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Coloring {
+    /// young cells are at the start of the gradient, old ones at its end
+    Age,
+    /// the more living neighbors, the further along the gradient
+    Neighbors,
+}
+
+impl Coloring {
+    const NAMES: &'static [&'static str] = &["age", "neighbors"];
+
+    fn name(&self) -> &'static str {
+        Coloring::NAMES[*self as usize]
+    }
+}
+
+/// the age of cells that have never been alive or died long ago
+const LONG_DEAD: u32 = u32::MAX;
 
 pub struct GOL {
-    data: Vec<bool>,
-    step_counter: usize,
-    speed: usize,
-    x: usize,
-    y: usize,
-    initialized: bool,
+    cells: Grid<bool>,
+    /// the number of generations every cell has been alive or dead
+    ages: Grid<u32>,
+    coloring: Coloring,
+    gradient: Gradient,
+    /// the number of generations dead cells are still shown
+    ghosts: u32,
+    /// the rule given as a parameter, it takes precedence over the one of the pattern file
+    rule: Option<Rule>,
+    /// whether the edges wrap around, otherwise everything outside of the frame is dead
+    wrap: bool,
+    /// the file of the starting pattern and the pattern, random cells are used without one
+    pattern: Option<(String, Pattern)>,
+    generations: Generations,
+    density: f32,
+    /// the hashes of the last generations, to find still lifes and oscillators
+    history: VecDeque<u64>,
+    /// the number of generations since the cells died out or started to repeat
+    stagnant: usize,
+    on_stagnation: Stagnation,
+    /// whether the animation asked to end
+    finished: bool,
+    rng: StdRng,
 }
 
 impl GOL {
-    const NAME: &'static str = "Game of Life";
-    const AUTHOR: &'static str = "Jo";
+    pub const NAME: &'static str = "Game of Life";
+    pub const AUTHOR: &'static str = "Jo";
 
+    /// the age at which living cells get the last color of the gradient
+    const OLD_AGE: u32 = 50;
+
+    /// computes the next generation.
     pub fn step(&mut self) {
-        // only make a game step every self.speed steps
-        if self.step_counter.rem_euclid(self.speed) != 0 {
-            self.step_counter += 1;
-            return;
+        let previous = self.cells.clone();
+        let rule = self.rule();
+        self.cells
+            .step(|cells, x, y| rule.next(cells.get(x, y), cells.count(x, y, true)));
+
+        let cells = &self.cells;
+        self.ages.step(|ages, x, y| {
+            if cells.get(x, y) == previous.get(x, y) {
+                ages.get(x, y).saturating_add(1)
+            } else {
+                1
+            }
+        });
+    }
+
+    /// sets a cell, its age starts over if it is born or dies.
+    fn set_cell(&mut self, x: usize, y: usize, alive: bool) {
+        if self.cells.get(x, y) != alive {
+            self.cells.set(x, y, alive);
+            self.ages.set(x, y, 1);
         }
-        self.step_counter += 1;
+    }
+
+    /// the cell as it is drawn, or `None` if nothing is drawn.
+    fn draw_cell(&self, x: usize, y: usize) -> Option<Character> {
+        let age = self.ages.get(x, y);
 
-        let mut new_data = vec![false; self.x * self.y];
+        if !self.cells.get(x, y) {
+            // a ghost that fades away
+            if age > self.ghosts {
+                return None;
+            }
+            let mut color = self.gradient.at(0.0);
+            color.scale(0.5 * (1.0 - age as f32 / (self.ghosts + 1) as f32));
+            return Some(Character {
+                character: '.',
+                color,
+                background: None,
+            });
+        }
 
-        for y in 0..self.y {
-            for x in 0..self.x {
-                let mut neighbors = 0;
-                for x_offset in -1..=1 {
-                    for y_offset in -1..=1 {
-                        // skip the cell itself
-                        if x_offset == 0 && y_offset == 0 {
-                            continue;
-                        }
+        let position = match self.coloring {
+            Coloring::Age => (age - 1) as f32 / GOL::OLD_AGE as f32,
+            Coloring::Neighbors => self.cells.count(x, y, true) as f32 / 8.0,
+        };
+        let character = match age {
+            1 => 'o',
+            2..=9 => '@',
+            _ => '#',
+        };
 
-                        let x_index = (x as i32 + x_offset).rem_euclid(self.x as i32) as usize;
-                        let y_index = (y as i32 + y_offset).rem_euclid(self.y as i32) as usize;
+        Some(Character {
+            character,
+            color: self.gradient.at(position),
+            background: None,
+        })
+    }
+
+    /// fills the frame with the pattern in its center, or randomly if there is none.
+    pub fn initialize(&mut self) {
+        self.history.clear();
+        self.stagnant = 0;
 
-                        if self.data[y_index * self.x + x_index] {
-                            neighbors += 1;
-                        }
-                    }
+        self.cells.fill(false);
+        self.ages.fill(LONG_DEAD);
+        let size = self.cells.size();
+
+        let Some((_, pattern)) = &self.pattern else {
+            for y in 0..size.height {
+                for x in 0..size.width {
+                    let alive = self.random_cell();
+                    self.set_cell(x, y, alive);
                 }
-                if neighbors == 2 && self.data[y * self.x + x] || neighbors == 3 {
-                    new_data[y * self.x + x] = true;
+            }
+            return;
+        };
+
+        // patterns that are larger than the frame are cut off on all sides
+        let left = size.width as isize / 2 - pattern.width as isize / 2;
+        let top = size.height as isize / 2 - pattern.height as isize / 2;
+        for y in 0..pattern.height {
+            for x in 0..pattern.width {
+                let frame_x = left + x as isize;
+                let frame_y = top + y as isize;
+                if (0..size.width as isize).contains(&frame_x)
+                    && (0..size.height as isize).contains(&frame_y)
+                    && pattern.get(x, y)
+                {
+                    self.cells.set(frame_x as usize, frame_y as usize, true);
+                    self.ages.set(frame_x as usize, frame_y as usize, 1);
                 }
             }
         }
+    }
+
+    /// Checks the current generation for stagnation and reacts to it after a while, so the
+    /// final state can be seen for a moment.
+    fn check_stagnation(&mut self) {
+        // oscillators with a longer period are rare in random soups
+        const HISTORY: usize = 16;
+        // about 4 seconds at the default speed
+        const PATIENCE: usize = 30;
 
-        self.data = new_data;
+        let mut hasher = DefaultHasher::new();
+        self.cells.cells().hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let extinct = !self.cells.cells().contains(&true);
+        if extinct || self.history.contains(&hash) {
+            self.stagnant += 1;
+        } else {
+            self.stagnant = 0;
+        }
+        if self.history.len() == HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(hash);
+
+        if self.stagnant < PATIENCE {
+            return;
+        }
+        match self.on_stagnation {
+            Stagnation::Reseed => self.initialize(),
+            Stagnation::Inject => self.inject(),
+            Stagnation::End => self.finished = true,
+            Stagnation::Ignore => (),
+        }
     }
 
-    pub fn initialize(&mut self) {
-        let mut rng = rand::thread_rng();
+    /// Fills a square at a random position with random cells.
+    fn inject(&mut self) {
+        const SIZE: usize = 8;
 
-        for cell in self.data.iter_mut() {
-            if rng.gen::<f32>() < 0.25 {
-                *cell = true;
+        let size = self.cells.size();
+        let left = self.rng.gen_range(0..size.width.max(1));
+        let top = self.rng.gen_range(0..size.height.max(1));
+        for y in top..(top + SIZE).min(size.height) {
+            for x in left..(left + SIZE).min(size.width) {
+                // denser than usual, so the patch does not die out immediately
+                let alive = self.rng.gen::<f32>() < self.density.max(0.4);
+                self.set_cell(x, y, alive);
             }
         }
+        self.stagnant = 0;
+    }
+
+    fn random_cell(&mut self) -> bool {
+        self.rng.gen::<f32>() < self.density
+    }
+
+    /// The rule given as a parameter, otherwise the one of the pattern file or Conway's.
+    fn rule(&self) -> Rule {
+        self.rule
+            .or_else(|| self.pattern.as_ref().and_then(|(_, pattern)| pattern.rule))
+            .unwrap_or(Rule::CONWAY)
+    }
+
+    /// Loads the starting pattern, an empty path goes back to random cells.
+    fn load_pattern(&mut self, path: &str) -> Result<(), String> {
+        if path.is_empty() {
+            self.pattern = None;
+            return Ok(());
+        }
+
+        let pattern = Pattern::load(Path::new(path)).map_err(|error| error.to_string())?;
+        self.pattern = Some((path.to_string(), pattern));
+        Ok(())
     }
 }
 
 impl Default for GOL {
     fn default() -> GOL {
         GOL {
-            data: vec![],
-            step_counter: 0,
-            speed: 8,
-            x: 0,
-            y: 0,
-            initialized: false,
+            cells: Grid::new(Size::new(0, 0), false, true),
+            ages: Grid::new(Size::new(0, 0), LONG_DEAD, true),
+            coloring: Coloring::Age,
+            gradient: "#a0f0ff,#ffffff,#ffd24d,#ff6a00,#c0206f".parse().unwrap(),
+            ghosts: 4,
+            rule: None,
+            wrap: true,
+            pattern: None,
+            generations: Generations::new(7.5),
+            density: 0.25,
+            history: VecDeque::new(),
+            stagnant: 0,
+            on_stagnation: Stagnation::Reseed,
+            finished: false,
+            rng: StdRng::from_entropy(),
         }
     }
 }
@@ -84,30 +297,326 @@ impl Animation for GOL {
         GOL::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        // fill the data if it is not big enough
-        while self.data.len() < frame.data.len() {
-            self.data.push(false);
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "generations per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 120.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "density",
+                description: "share of living cells at the start",
+                kind: Kind::Float { min: 0.0, max: 1.0 },
+                value: Value::Float(self.density as f64),
+            },
+            Parameter {
+                name: "rule",
+                description: "Life-like rule in B/S notation, like B36/S23, or its name",
+                kind: Kind::Text,
+                value: Value::Text(self.rule().to_string()),
+            },
+            Parameter {
+                name: "wrap",
+                description: "whether the edges wrap around",
+                kind: Kind::Bool,
+                value: Value::Bool(self.wrap),
+            },
+            Parameter {
+                name: "pattern",
+                description: "RLE or .cells file to start with instead of random cells",
+                kind: Kind::Text,
+                value: Value::Text(
+                    self.pattern
+                        .as_ref()
+                        .map_or(String::new(), |(path, _)| path.clone()),
+                ),
+            },
+            Parameter {
+                name: "stagnation",
+                description: "what happens when the cells die out or only repeat themselves",
+                kind: Kind::Choice(Stagnation::NAMES),
+                value: Value::Text(self.on_stagnation.name().to_string()),
+            },
+            Parameter {
+                name: "colors",
+                description: "whether the colors show the age or the number of neighbors",
+                kind: Kind::Choice(Coloring::NAMES),
+                value: Value::Text(self.coloring.name().to_string()),
+            },
+            Parameter {
+                name: "gradient",
+                description: "colors from young to old cells, like #ffffff,#ff8000",
+                kind: Kind::Text,
+                value: Value::Text(self.gradient.to_string()),
+            },
+            Parameter {
+                name: "ghosts",
+                description: "generations that dead cells are still shown",
+                kind: Kind::Integer { min: 0, max: 20 },
+                value: Value::Integer(self.ghosts as i64),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "density" => self.density = value.as_float().unwrap() as f32,
+            "rule" => {
+                if let Ok(rule) = value.as_text().unwrap().parse() {
+                    self.rule = Some(rule);
+                }
+            }
+            "wrap" => {
+                self.wrap = value.as_bool().unwrap();
+                self.cells.wrap = self.wrap;
+            }
+            "colors" => {
+                self.coloring = match value.as_text().unwrap() {
+                    "neighbors" => Coloring::Neighbors,
+                    _ => Coloring::Age,
+                }
+            }
+            "gradient" => {
+                if let Ok(gradient) = value.as_text().unwrap().parse() {
+                    self.gradient = gradient;
+                }
+            }
+            "ghosts" => self.ghosts = value.as_integer().unwrap() as u32,
+            "stagnation" => {
+                self.on_stagnation = Stagnation::from_name(value.as_text().unwrap()).unwrap()
+            }
+            "pattern" => {
+                let _ = self.load_pattern(value.as_text().unwrap());
+            }
+            _ => (),
+        }
+    }
+
+    /// rules and pattern files are checked, so mistakes are reported instead of ignored.
+    fn set_parameter_from_str(&mut self, name: &str, text: &str) -> Result<(), ParameterError> {
+        let value = parameters::parse(&self.parameters(), name, text)?;
+        let invalid = |expected: String| ParameterError::Invalid {
+            name: name.to_string(),
+            value: text.to_string(),
+            expected,
+        };
+
+        match name {
+            "rule" => {
+                self.rule = Some(text.trim().parse().map_err(|_| {
+                    invalid(
+                        "a rule in B/S notation like B3/S23 or a name like highlife".to_string(),
+                    )
+                })?)
+            }
+            "gradient" => self.gradient = text.parse().map_err(invalid)?,
+            "pattern" => self
+                .load_pattern(text.trim())
+                .map_err(|error| invalid(format!("a pattern file ({})", error)))?,
+            _ => self.set_parameter(name, value),
+        }
+        Ok(())
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, false, self.wrap);
+        self.ages = Grid::new(size, LONG_DEAD, self.wrap);
+        self.initialize();
+    }
+
+    fn resize(&mut self, _old: Size, new: Size) {
+        // keep the cells that are still visible, the new area is filled randomly without a pattern
+        let random = self.pattern.is_none();
+        let (rng, density) = (&mut self.rng, self.density);
+        self.cells
+            .resize(new, |_, _| random && rng.gen::<f32>() < density);
+
+        let cells = &self.cells;
+        self.ages
+            .resize(new, |x, y| if cells.get(x, y) { 1 } else { LONG_DEAD });
+        self.history.clear();
+        self.stagnant = 0;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, y }) = *event else {
+            return;
+        };
+        let size = self.cells.size();
+        if x >= size.width || y >= size.height {
+            return;
+        }
+
+        // a click toggles the cell, dragging draws living cells
+        match kind {
+            MouseKind::Press(Button::Left) => self.set_cell(x, y, !self.cells.get(x, y)),
+            MouseKind::Drag(Button::Left) => self.set_cell(x, y, true),
+            _ => (),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            self.step();
+            self.check_stagnation();
+        }
+
+        let size = self.cells.size();
+        for y in 0..size.height {
+            for x in 0..size.width {
+                if let Some(character) = self.draw_cell(x, y) {
+                    frame.set_at(x, y, character);
+                }
+            }
         }
-        self.x = frame.x;
-        self.y = frame.y;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn alive(gol: &GOL) -> Vec<(usize, usize)> {
+        let width = gol.cells.size().width;
+        (0..gol.cells.cells().len())
+            .filter(|&index| gol.cells.cells()[index])
+            .map(|index| (index % width, index / width))
+            .collect()
+    }
 
-        // initialize, if not done yet
-        if !self.initialized {
-            self.initialize();
-            self.initialized = true;
+    #[test]
+    fn test_pattern() {
+        let glider = concat!(env!("CARGO_MANIFEST_DIR"), "/patterns/glider.cells");
+        let mut gol = GOL::default();
+        gol.set_parameter_from_str("pattern", glider).unwrap();
+        gol.init(Size::new(6, 6));
+        assert_eq!(alive(&gol), [(3, 2), (4, 3), (2, 4), (3, 4), (4, 4)]);
+
+        // the glider moves one cell diagonally every 4 generations and wraps around
+        for _ in 0..8 {
+            gol.step();
+        }
+        assert_eq!(alive(&gol), [(0, 0), (4, 0), (5, 0), (5, 4), (0, 5)]);
+
+        // without wrapping it turns into a block in the corner
+        gol.set_parameter_from_str("wrap", "false").unwrap();
+        gol.init(Size::new(6, 6));
+        for _ in 0..12 {
+            gol.step();
         }
+        assert_eq!(alive(&gol), [(4, 4), (5, 4), (4, 5), (5, 5)]);
+    }
 
-        self.step();
+    #[test]
+    fn test_stagnation() {
+        let mut gol = GOL::default();
+        gol.set_parameter_from_str("stagnation", "end").unwrap();
+        gol.set_parameter_from_str("density", "0").unwrap();
+        gol.init(Size::new(5, 5));
+
+        // a blinker
+        for x in 1..4 {
+            gol.set_cell(x, 2, true);
+        }
+        let mut generations = 0;
+        while !gol.is_finished() {
+            gol.step();
+            gol.check_stagnation();
+            generations += 1;
+            assert!(generations < 100, "the blinker was not detected");
+        }
 
-        // draw
-        for (index, cell) in self.data.iter().enumerate() {
-            if *cell {
-                frame.data[index] = Character {
-                    character: '@',
-                    color: Color::white(),
-                };
+        // an empty field is reseeded with the density
+        gol.set_parameter_from_str("stagnation", "reseed").unwrap();
+        gol.set_parameter_from_str("density", "0.5").unwrap();
+        gol.cells.fill(false);
+        for _ in 0..generations {
+            gol.check_stagnation();
+        }
+        assert!(gol.cells.cells().contains(&true));
+    }
+
+    #[test]
+    fn test_ages() {
+        let mut gol = GOL::default();
+        gol.set_parameter_from_str("density", "0").unwrap();
+        gol.set_parameter_from_str("gradient", "#ffffff").unwrap();
+        gol.init(Size::new(5, 5));
+
+        // a blinker, its center stays alive while the ends switch
+        for x in 1..4 {
+            gol.set_cell(x, 2, true);
+        }
+        gol.step();
+        gol.step();
+        assert_eq!(gol.ages.get(2, 2), 3);
+        assert_eq!(gol.ages.get(1, 2), 1);
+
+        let cell = |gol: &GOL, x, y| gol.draw_cell(x, y).map(|cell| cell.character);
+        assert_eq!(cell(&gol, 2, 2), Some('@'));
+        assert_eq!(cell(&gol, 1, 2), Some('o'));
+        // died in the last generation
+        assert_eq!(cell(&gol, 2, 1), Some('.'));
+        assert_eq!(cell(&gol, 0, 0), None);
+
+        gol.set_parameter_from_str("ghosts", "0").unwrap();
+        assert_eq!(cell(&gol, 2, 1), None);
+        assert!(gol.set_parameter_from_str("gradient", "red").is_err());
+    }
+
+    #[test]
+    fn test_parameters() {
+        let mut gol = GOL::default();
+        gol.set_parameter_from_str("rule", "highlife").unwrap();
+        assert_eq!(gol.rule().to_string(), "B36/S23");
+
+        assert!(gol.set_parameter_from_str("rule", "B9/S").is_err());
+        assert!(gol
+            .set_parameter_from_str("pattern", "missing.rle")
+            .is_err());
+        assert_eq!(gol.rule().to_string(), "B36/S23");
+        assert!(gol.pattern.is_none());
+    }
+
+    #[test]
+    fn test_pattern_rule() {
+        let gun = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/patterns/gosper-glider-gun.rle"
+        );
+
+        // the rule of the file is only used without an explicit one, no matter the order
+        for parameters in [
+            [("rule", "highlife"), ("pattern", gun)],
+            [("pattern", gun), ("rule", "highlife")],
+        ] {
+            let mut gol = GOL::default();
+            for (name, value) in parameters {
+                gol.set_parameter_from_str(name, value).unwrap();
             }
+            assert_eq!(gol.rule().to_string(), "B36/S23");
         }
+
+        // otherwise it is
+        let mut gol = GOL::default();
+        let pattern = Pattern::parse("x = 1, y = 1, rule = B2/S\no!").unwrap();
+        gol.pattern = Some(("seed.rle".to_string(), pattern));
+        assert_eq!(gol.rule().to_string(), "B2/S");
+        gol.set_parameter_from_str("rule", "highlife").unwrap();
+        assert_eq!(gol.rule().to_string(), "B36/S23");
     }
 }
@@ -5,33 +5,65 @@ use crate::frame::Character;
 use nalgebra::{Rotation2, Scale2, Vector2};
 
 /// describes an array of Hexagons.
-/// r is the radius from the center of a hexagon to one of the corners.
+/// r is the radius from the center of a hexagon to one of the corners,
+/// it oscillates around `radius`.
 pub struct Hexagons {
-    step: usize,
+    /// seconds since the start
+    time: f32,
     r: f32,
+    radius: f32,
 }
 
 impl Default for Hexagons {
     fn default() -> Self {
-        Hexagons { step: 0, r: 5.0 }
+        Hexagons {
+            time: 0.0,
+            r: 5.0,
+            radius: 5.0,
+        }
     }
 }
 
+impl Hexagons {
+    pub const NAME: &'static str = "Hexagons";
+    pub const AUTHOR: &'static str = "Jo";
+}
+
 impl Animation for Hexagons {
     fn name(&self) -> &'static str {
-        "Hexagons"
+        Hexagons::NAME
     }
 
     fn author(&self) -> &'static str {
-        "Jo"
+        Hexagons::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![Parameter {
+            name: "radius",
+            description: "radius of the hexagons",
+            kind: Kind::Float {
+                min: 2.0,
+                max: 50.0,
+            },
+            value: Value::Float(self.radius as f64),
+        }]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        if name == "radius" {
+            self.radius = value.as_float().unwrap() as f32;
+        }
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        self.step += 1;
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.time += time.delta;
 
-        let angle = self.step as f32 * std::f32::consts::PI * 2.0 / 360.0 * 0.1;
+        // one rotation per minute
+        let angle = self.time * std::f32::consts::PI * 2.0 / 60.0;
 
-        self.r = (self.step as f32 * std::f32::consts::PI / 360.0).sin() + 5.0;
+        // the radius oscillates with a period of 12 seconds
+        self.r = (self.time * std::f32::consts::PI / 6.0).sin() + self.radius;
 
         let b = self.r * (3.0f32 / 4.0).sqrt();
 
@@ -81,7 +113,7 @@ impl Animation for Hexagons {
 
         let frame_width = frame.x as f32;
 
-        let color_func = |x: isize, y: isize| {
+        let color_func = |x: isize, _y: isize| {
             let value = x as f32 / frame_width;
             let red = Color {
                 r: 1.0,
@@ -96,6 +128,7 @@ impl Animation for Hexagons {
             Character {
                 character: 'o',
                 color: red.interpolate(&blue, value),
+                background: None,
             }
         };
 
@@ -115,9 +148,10 @@ impl Animation for Hexagons {
                 }
 
                 for (start, end) in lines {
-                    match clip(start, end, 0.0, frame.x as f32, 0.0, frame.y as f32) {
-                        Some((start, end)) => plot_line(frame, start, end, &color_func),
-                        None => (),
+                    if let Some((start, end)) =
+                        clip(start, end, 0.0, frame.x as f32, 0.0, frame.y as f32)
+                    {
+                        plot_line(frame, start, end, &color_func)
                     }
                 }
             }
@@ -1,6 +1,5 @@
 use super::prelude::*;
 use nalgebra::Vector2;
-use rand::Rng;
 
 struct Hill {
     pos: Vector2<f32>,
@@ -10,11 +9,10 @@ struct Hill {
 }
 
 impl Hill {
-    fn random(x: f32, y: f32) -> Hill {
-        let mut rng = rand::thread_rng();
-
+    fn random(x: f32, y: f32, rng: &mut impl Rng) -> Hill {
         let pos = Vector2::new(rng.gen_range(0.0..(x + 1.0)), rng.gen_range(0.0..(y + 1.0)));
-        let direction = Vector2::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 0.2;
+        // cells per second
+        let direction = Vector2::new(rng.gen::<f32>() - 0.5, rng.gen::<f32>() - 0.5) * 12.0;
 
         let size = rng.gen::<f32>() * (x.min(y));
         let height = rng.gen::<f32>() * 0.5;
@@ -37,43 +35,29 @@ impl Hill {
 
 pub struct Hills {
     hills: Vec<Hill>,
-    initialized: bool,
     x: f32,
     y: f32,
+    rng: StdRng,
 }
 
 impl Default for Hills {
     fn default() -> Self {
         Hills {
             hills: Vec::new(),
-            initialized: false,
             x: 0.0,
             y: 0.0,
+            rng: StdRng::from_entropy(),
         }
     }
 }
 
 impl Hills {
-    const NAME: &'static str = "Hills";
-    const AUTHOR: &'static str = "Jo";
-
-    fn initialize(&mut self, frame: &Frame) {
-        if self.initialized {
-            return;
-        };
-
-        self.x = frame.x as f32;
-        self.y = frame.y as f32;
-
-        for _ in 0..10 {
-            self.hills.push(Hill::random(self.x, self.y));
-        }
-        self.initialized = true;
-    }
+    pub const NAME: &'static str = "Hills";
+    pub const AUTHOR: &'static str = "Jo";
 
-    fn step(&mut self, frame: &Frame) {
+    fn step(&mut self, frame: &Frame, delta: f32) {
         for hill in self.hills.iter_mut() {
-            hill.pos += hill.direction;
+            hill.pos += hill.direction * delta;
             hill.pos.x = hill.pos.x.rem_euclid(frame.x as f32 + 1.0);
             hill.pos.y = hill.pos.y.rem_euclid(frame.y as f32 + 1.0);
         }
@@ -109,9 +93,36 @@ impl Animation for Hills {
         Hills::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        self.initialize(frame);
-        self.step(frame);
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn init(&mut self, size: Size) {
+        self.x = size.width as f32;
+        self.y = size.height as f32;
+
+        self.hills.clear();
+        for _ in 0..10 {
+            self.hills.push(Hill::random(self.x, self.y, &mut self.rng));
+        }
+    }
+
+    fn resize(&mut self, _old: Size, new: Size) {
+        // keep the hills at the same relative position
+        let x = new.width as f32;
+        let y = new.height as f32;
+
+        for hill in self.hills.iter_mut() {
+            hill.pos.x *= (x + 1.0) / (self.x + 1.0);
+            hill.pos.y *= (y + 1.0) / (self.y + 1.0);
+        }
+
+        self.x = x;
+        self.y = y;
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.step(frame, time.delta);
 
         for x in 0..frame.x {
             for y in 0..frame.y {
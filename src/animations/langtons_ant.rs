@@ -0,0 +1,220 @@
+use super::prelude::*;
+use crate::automaton::{Generations, Grid};
+
+/// the offsets of up, right, down and left
+const DIRECTIONS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ant {
+    x: usize,
+    y: usize,
+    /// an index into `DIRECTIONS`
+    direction: usize,
+}
+
+/// Langton's ant, ants that turn right on white cells and left on black ones and flip them.
+pub struct LangtonsAnt {
+    /// 0 for white cells, otherwise the number of the ant that made it black plus one
+    cells: Grid<u8>,
+    ants: Vec<Ant>,
+    /// the number of ants at the start
+    count: usize,
+    /// the hue of the first ant, the others are spread evenly
+    hue: f32,
+    generations: Generations,
+    rng: StdRng,
+}
+
+impl LangtonsAnt {
+    pub const NAME: &'static str = "Langton's Ant";
+    pub const AUTHOR: &'static str = "Jo";
+
+    /// the most ants at once, clicks do not add more
+    const MAX_ANTS: usize = 16;
+
+    /// moves every ant once.
+    fn step(&mut self) {
+        for (index, ant) in self.ants.iter_mut().enumerate() {
+            if self.cells.get(ant.x, ant.y) == 0 {
+                ant.direction = (ant.direction + 1) % 4;
+                self.cells.set(ant.x, ant.y, index as u8 + 1);
+            } else {
+                ant.direction = (ant.direction + 3) % 4;
+                self.cells.set(ant.x, ant.y, 0);
+            }
+
+            let (dx, dy) = DIRECTIONS[ant.direction];
+            // the grid wraps, so there always is a next cell
+            (ant.x, ant.y) = self.cells.offset(ant.x, ant.y, dx, dy).unwrap();
+        }
+    }
+
+    fn initialize(&mut self) {
+        self.cells.fill(0);
+        self.ants.clear();
+        self.hue = self.rng.gen();
+
+        let size = self.cells.size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        for _ in 0..self.count {
+            self.ants.push(Ant {
+                x: self.rng.gen_range(0..size.width),
+                y: self.rng.gen_range(0..size.height),
+                direction: self.rng.gen_range(0..4),
+            });
+        }
+    }
+
+    fn color(&self, ant: usize) -> Color {
+        HSVColor {
+            h: (self.hue + ant as f32 / self.ants.len().max(1) as f32).fract(),
+            s: 0.8,
+            v: 0.9,
+        }
+        .into()
+    }
+}
+
+impl Default for LangtonsAnt {
+    fn default() -> LangtonsAnt {
+        LangtonsAnt {
+            cells: Grid::new(Size::new(0, 0), 0, true),
+            ants: Vec::new(),
+            count: 3,
+            hue: 0.0,
+            generations: Generations::new(150.0),
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Animation for LangtonsAnt {
+    fn name(&self) -> &'static str {
+        LangtonsAnt::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        LangtonsAnt::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "steps of every ant per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 2000.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "ants",
+                description: "ants at the start",
+                kind: Kind::Integer {
+                    min: 1,
+                    max: LangtonsAnt::MAX_ANTS as i64,
+                },
+                value: Value::Integer(self.count as i64),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "ants" => self.count = value.as_integer().unwrap() as usize,
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, 0, true);
+        self.initialize();
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, y }) = *event else {
+            return;
+        };
+        let size = self.cells.size();
+        if x >= size.width || y >= size.height || self.ants.len() >= LangtonsAnt::MAX_ANTS {
+            return;
+        }
+
+        // a click sets down another ant
+        if kind == MouseKind::Press(Button::Left) {
+            let direction = self.rng.gen_range(0..4);
+            self.ants.push(Ant { x, y, direction });
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            self.step();
+        }
+
+        self.cells.draw(frame, |cell| {
+            (cell != 0).then(|| Character {
+                character: '#',
+                color: self.color(cell as usize - 1),
+                background: None,
+            })
+        });
+        for ant in self.ants.iter() {
+            frame.set_at(
+                ant.x,
+                ant.y,
+                Character {
+                    character: '@',
+                    color: Color::white(),
+                    background: None,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let mut langton = LangtonsAnt {
+            cells: Grid::new(Size::new(5, 5), 0, true),
+            ants: vec![Ant {
+                x: 2,
+                y: 2,
+                direction: 0,
+            }],
+            ..LangtonsAnt::default()
+        };
+
+        // on white cells the ant turns right, four steps bring it back to where it started
+        for _ in 0..4 {
+            langton.step();
+        }
+        assert_eq!((langton.ants[0].x, langton.ants[0].y), (2, 2));
+        assert_eq!(
+            langton
+                .cells
+                .cells()
+                .iter()
+                .filter(|&&cell| cell != 0)
+                .count(),
+            4
+        );
+
+        // now it is on a black cell and turns left
+        langton.step();
+        assert_eq!(langton.ants[0].direction, 3);
+        assert_eq!(langton.cells.get(2, 2), 0);
+    }
+}
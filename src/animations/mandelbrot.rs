@@ -1,27 +1,115 @@
 use super::Animation;
-use crate::frame::{value_to_char, Character, Color, Frame, HSVColor};
+use crate::canvas::{Canvas, Resolution, Surface};
+use crate::clock::Time;
+use crate::frame::{value_to_char, Character, Color, Frame, HSVColor, Size};
+use crate::input::{Button, Event, Mouse, MouseKind};
+use crate::parameters::{Kind, Parameter, Value};
 use nalgebra::{Complex, ComplexField};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::TAU;
+
+/// Points on the border of the set that stay detailed all the way down to the precision of `f64`.
+///
+/// Most of them are Misiurewicz points, whose surroundings look alike at every zoom level.
+const POINTS: &[(&str, f64, f64)] = &[
+    ("spirals", -0.608118878, -0.615161994),
+    ("twin-spirals", -0.5062944581958893, 0.683991968089373),
+    ("triple-spirals", -0.6070310226160881, 0.6052513812789341),
+    ("branches", -0.10109636384562216, 0.9562865108091416),
+    ("east-spirals", 0.36636298342276435, 0.5915337732614453),
+    ("west-spirals", -1.2228637871299002, 0.31688226388713636),
+    ("dendrite", 0.0, 1.0),
+];
+
+/// Which point is zoomed into.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    /// a random one of `POINTS`, a different one after every reset
+    Random,
+    /// the point given by the center parameter
+    Custom,
+    /// an index into `POINTS`
+    Point(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Mandelbrot,
+    /// the Julia set of a constant that orbits around the origin
+    Julia,
+}
+
+impl Mode {
+    const NAMES: &'static [&'static str] = &["mandelbrot", "julia"];
+}
 
 pub struct Mandelbrot {
     width: f64,
+    /// whether the width was given as a parameter, otherwise Julia sets start with the whole set
+    width_set: bool,
+    center: Complex<f64>,
+    target: Target,
+    /// factor the width is multiplied with every second
+    zoom: f64,
+    mode: Mode,
+    /// the angle of the constant of the Julia set on its orbit
+    angle: f64,
+    /// radians per second the constant of the Julia set moves along its orbit
+    orbit: f64,
+    size: Size,
+    resolution: Resolution,
+    rng: StdRng,
 }
 
 impl Default for Mandelbrot {
     fn default() -> Mandelbrot {
-        Mandelbrot { width: 8.0 }
+        Mandelbrot {
+            width: Mandelbrot::START_WIDTH,
+            width_set: false,
+            // replaced by the target in init()
+            center: Complex::new(0.0, 0.0),
+            target: Target::Random,
+            zoom: 0.4,
+            mode: Mode::Mandelbrot,
+            angle: 0.0,
+            orbit: 0.1,
+            size: Size::new(0, 0),
+            resolution: Resolution::Text,
+            rng: StdRng::from_entropy(),
+        }
     }
 }
 
 impl Mandelbrot {
-    const NAME: &'static str = "Mandelbrot";
-    const AUTHOR: &'static str = "Marco";
+    pub const NAME: &'static str = "Mandelbrot";
+    pub const AUTHOR: &'static str = "Marco";
     const BOUND: f64 = 2.0;
 
-    fn eval(&mut self, c: Complex<f64>) -> i32 {
-        let mut z = Complex::new(0.0, 0.0);
+    /// the names for the target parameter, followed by the names of `POINTS`
+    const TARGETS: &'static [&'static str] = &{
+        let mut targets = ["random"; POINTS.len() + 2];
+        targets[1] = "custom";
+        let mut index = 0;
+        while index < POINTS.len() {
+            targets[index + 2] = POINTS[index].0;
+            index += 1;
+        }
+        targets
+    };
+
+    const START_WIDTH: f64 = 8.0;
+    /// below this width neighboring pixels can no longer be told apart with `f64`
+    const MIN_WIDTH: f64 = 1e-11;
 
-        let max_iterations = self.calculate_max_iterations();
+    /// the width that shows the whole Julia set
+    const JULIA_WIDTH: f64 = 3.2;
+    /// the distance of the constant of the Julia set from the origin, close to the border of the
+    /// Mandelbrot set for most angles
+    const JULIA_RADIUS: f64 = 0.7885;
 
+    /// the number of iterations until `z` escapes, `max_iterations` if it does not.
+    fn eval(&self, mut z: Complex<f64>, c: Complex<f64>, max_iterations: i32) -> i32 {
         for iteration in 0..max_iterations {
             z = z.powi(2) + c;
 
@@ -33,6 +121,41 @@ impl Mandelbrot {
         max_iterations
     }
 
+    /// the constant of the Julia set
+    fn julia_constant(&self) -> Complex<f64> {
+        Complex::new(self.angle.cos(), self.angle.sin()) * Mandelbrot::JULIA_RADIUS
+    }
+
+    /// moves the center to the next point according to the target.
+    fn retarget(&mut self) {
+        match self.target {
+            Target::Custom => (),
+            Target::Point(index) => {
+                let (_, re, im) = POINTS[index];
+                self.center = Complex::new(re, im);
+            }
+            Target::Random => {
+                // a different point than the current one
+                let others: Vec<Complex<f64>> = POINTS
+                    .iter()
+                    .map(|&(_, re, im)| Complex::new(re, im))
+                    .filter(|point| *point != self.center)
+                    .collect();
+                self.center = others[self.rng.gen_range(0..others.len())];
+            }
+        }
+    }
+
+    /// the point of the complex plane that is shown at the given position in cells
+    fn point(&self, size: Size, x: f64, y: f64) -> Complex<f64> {
+        let height = self.width * (size.height as f64 / size.width as f64) * 2.5;
+
+        Complex::new(
+            (x / size.width as f64) * self.width - self.width / 2.0 + self.center.re,
+            (y / size.height as f64) * height - height / 2.0 + self.center.im,
+        )
+    }
+
     fn calculate_max_iterations(&self) -> i32 {
         (50.0 + (4.0 / self.width).log10().powi(2)).round() as i32
     }
@@ -47,22 +170,165 @@ impl Animation for Mandelbrot {
         Mandelbrot::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        let height = self.width * (frame.y as f64 / frame.x as f64) * 2.5;
-        let center = Complex::new(-0.608118878, -0.615161994);
+    fn parameters(&self) -> Vec<Parameter> {
+        let target = match self.target {
+            Target::Random => "random",
+            Target::Custom => "custom",
+            Target::Point(index) => POINTS[index].0,
+        };
 
-        for y in 0..frame.y {
-            let current_im = (y as f64 / frame.y as f64) * height - height / 2.0 + center.im;
+        vec![
+            Parameter {
+                name: "mode",
+                description: "the Mandelbrot set or a Julia set with a moving constant",
+                kind: Kind::Choice(Mode::NAMES),
+                value: Value::Text(Mode::NAMES[self.mode as usize].to_string()),
+            },
+            Parameter {
+                name: "target",
+                description: "a named point to zoom into, random ones or custom for the center",
+                kind: Kind::Choice(Mandelbrot::TARGETS),
+                value: Value::Text(target.to_string()),
+            },
+            Parameter {
+                name: "center",
+                description: "the point that is zoomed into, sets the target to custom",
+                kind: Kind::Point,
+                value: Value::Point(self.center.re, self.center.im),
+            },
+            Parameter {
+                name: "zoom",
+                description: "factor the visible width is multiplied with every second",
+                kind: Kind::Float {
+                    min: 0.01,
+                    max: 1.0,
+                },
+                value: Value::Float(self.zoom),
+            },
+            Parameter {
+                name: "width",
+                description: "the currently visible width",
+                kind: Kind::Float {
+                    min: 1e-12,
+                    max: 16.0,
+                },
+                value: Value::Float(self.width),
+            },
+            Parameter {
+                name: "orbit",
+                description: "radians per second the constant of the Julia set moves",
+                kind: Kind::Float { min: 0.0, max: 2.0 },
+                value: Value::Float(self.orbit),
+            },
+            Parameter {
+                name: "resolution",
+                description: "pixels per character",
+                kind: Kind::Choice(Resolution::NAMES),
+                value: Value::Text(self.resolution.name().to_string()),
+            },
+        ]
+    }
 
-            for x in 0..frame.x {
-                let current_re =
-                    (x as f64 / frame.x as f64) * self.width - self.width / 2.0 + center.re;
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
 
-                let current_coord = Complex::new(current_re, current_im);
-                let iterations_until_diverged = self.eval(current_coord);
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "mode" => {
+                self.mode = match value.as_text().unwrap() {
+                    "julia" => Mode::Julia,
+                    _ => Mode::Mandelbrot,
+                }
+            }
+            "target" => {
+                self.target = match value.as_text().unwrap() {
+                    "random" => Target::Random,
+                    "custom" => Target::Custom,
+                    name => Target::Point(
+                        POINTS
+                            .iter()
+                            .position(|(point, ..)| *point == name)
+                            .unwrap(),
+                    ),
+                };
+            }
+            "center" => {
+                let (re, im) = value.as_point().unwrap();
+                self.center = Complex::new(re, im);
+                self.target = Target::Custom;
+            }
+            "zoom" => self.zoom = value.as_float().unwrap(),
+            "width" => {
+                self.width = value.as_float().unwrap();
+                self.width_set = true;
+            }
+            "orbit" => self.orbit = value.as_float().unwrap(),
+            "resolution" => self.resolution = value.as_text().unwrap().parse().unwrap(),
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.size = size;
+        self.angle = self.rng.gen_range(0.0..TAU);
+        match self.mode {
+            Mode::Mandelbrot => self.retarget(),
+            // the whole Julia set around the origin, unless the view was given
+            Mode::Julia => {
+                if self.target != Target::Custom {
+                    self.center = Complex::new(0.0, 0.0);
+                }
+                if !self.width_set {
+                    self.width = Mandelbrot::JULIA_WIDTH;
+                }
+            }
+        }
+    }
+
+    fn resize(&mut self, _old: Size, new: Size) {
+        self.size = new;
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, y }) = *event else {
+            return;
+        };
+        if x >= self.size.width || y >= self.size.height {
+            return;
+        }
+
+        // a click zooms into the clicked point, the wheel zooms in and out by hand
+        match kind {
+            MouseKind::Press(Button::Left) => {
+                self.center = self.point(self.size, x as f64, y as f64)
+            }
+            MouseKind::ScrollUp => self.width *= 0.8,
+            MouseKind::ScrollDown => self.width = (self.width * 1.25).min(16.0),
+            _ => (),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        let size = frame.size();
+        let mut canvas = Canvas::new(size, self.resolution);
+        let (scale_x, scale_y) = self.resolution.scale();
+        let pixels = canvas.size();
+        let max_iterations = self.calculate_max_iterations();
+        let julia = self.julia_constant();
+
+        for y in 0..pixels.height {
+            for x in 0..pixels.width {
+                let current_coord =
+                    self.point(size, x as f64 / scale_x as f64, y as f64 / scale_y as f64);
+                let iterations_until_diverged = match self.mode {
+                    Mode::Mandelbrot => {
+                        self.eval(Complex::new(0.0, 0.0), current_coord, max_iterations)
+                    }
+                    Mode::Julia => self.eval(current_coord, julia, max_iterations),
+                };
 
                 let num_colors = 30;
-                let max_iterations = self.calculate_max_iterations();
                 let value = if iterations_until_diverged == max_iterations {
                     1.0
                 } else {
@@ -71,17 +337,108 @@ impl Animation for Mandelbrot {
 
                 let the_char = value_to_char(value);
 
-                frame.data[y * frame.x + x] = Character {
-                    character: the_char,
-                    color: Color::from(HSVColor {
-                        h: value.rem_euclid(1.0),
-                        s: 1.0,
-                        v: 1.0,
-                    }),
+                canvas.set_at_clipping(
+                    x as isize,
+                    y as isize,
+                    Character {
+                        character: the_char,
+                        color: Color::from(HSVColor {
+                            h: value.rem_euclid(1.0),
+                            s: 1.0,
+                            v: 1.0,
+                        }),
+                        background: None,
+                    },
+                );
+            }
+        }
+        canvas.draw(frame);
+
+        match self.mode {
+            Mode::Mandelbrot => {
+                self.width *= self.zoom.powf(time.delta as f64);
+                // start over before the precision runs out
+                if self.width < Mandelbrot::MIN_WIDTH {
+                    self.width = Mandelbrot::START_WIDTH;
+                    self.retarget();
                 }
             }
+            Mode::Julia => self.angle = (self.angle + self.orbit * time.delta as f64) % TAU,
         }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parameters::Value;
 
-        self.width *= 0.985;
+    fn time(delta: f32) -> Time {
+        Time {
+            elapsed: delta,
+            delta,
+            frame: 1,
+        }
+    }
+
+    #[test]
+    fn test_targets() {
+        let names: Vec<&str> = POINTS.iter().map(|(name, ..)| *name).collect();
+        assert_eq!(Mandelbrot::TARGETS[2..], names);
+
+        let mut mandelbrot = Mandelbrot::default();
+        mandelbrot.set_parameter("target", Value::Text("dendrite".to_string()));
+        mandelbrot.init(Size::new(20, 10));
+        assert_eq!(mandelbrot.center, Complex::new(0.0, 1.0));
+
+        mandelbrot.set_parameter("center", Value::Point(0.25, 0.0));
+        mandelbrot.init(Size::new(20, 10));
+        assert_eq!(mandelbrot.center, Complex::new(0.25, 0.0));
+    }
+
+    #[test]
+    fn test_retarget() {
+        let mut mandelbrot = Mandelbrot::default();
+        mandelbrot.seed(1);
+        mandelbrot.init(Size::new(20, 10));
+        let center = mandelbrot.center;
+        assert!(POINTS
+            .iter()
+            .any(|&(_, re, im)| Complex::new(re, im) == center));
+
+        // the next frame would be too deep for f64
+        mandelbrot.width = Mandelbrot::MIN_WIDTH * 1.1;
+        mandelbrot.render(&mut Frame::new(20, 10), &time(1.0));
+        assert_eq!(mandelbrot.width, Mandelbrot::START_WIDTH);
+        assert_ne!(mandelbrot.center, center);
+    }
+
+    #[test]
+    fn test_julia() {
+        let mut mandelbrot = Mandelbrot::default();
+        mandelbrot.set_parameter("mode", Value::Text("julia".to_string()));
+        mandelbrot.init(Size::new(20, 10));
+        let angle = mandelbrot.angle;
+
+        mandelbrot.render(&mut Frame::new(20, 10), &time(1.0));
+        // the view stays, the constant moves on
+        assert_eq!(mandelbrot.width, Mandelbrot::JULIA_WIDTH);
+        assert_eq!(mandelbrot.center, Complex::new(0.0, 0.0));
+        assert!((mandelbrot.julia_constant().abs() - Mandelbrot::JULIA_RADIUS).abs() < 1e-9);
+        assert_ne!(mandelbrot.angle, angle);
+
+        // an explicit view is kept, no matter the order of the parameters
+        for parameters in [
+            [("width", "0.5"), ("center", "0.1,0.2"), ("mode", "julia")],
+            [("mode", "julia"), ("width", "0.5"), ("center", "0.1,0.2")],
+        ] {
+            let mut mandelbrot = Mandelbrot::default();
+            for (name, value) in parameters {
+                mandelbrot.set_parameter_from_str(name, value).unwrap();
+            }
+            mandelbrot.init(Size::new(20, 10));
+            assert_eq!(mandelbrot.width, 0.5);
+            assert_eq!(mandelbrot.center, Complex::new(0.1, 0.2));
+        }
     }
 }
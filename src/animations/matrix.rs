@@ -1,13 +1,23 @@
 use super::prelude::*;
 use crate::frame::Character;
-use rand::Rng;
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct Matrix {
     columns: Vec<Column>,
-    initialized: bool,
     special_char_mode: SpecialCharMode,
     text: Vec<char>,
+    rng: StdRng,
+}
+
+impl Default for Matrix {
+    fn default() -> Matrix {
+        Matrix {
+            columns: Vec::new(),
+            special_char_mode: SpecialCharMode::default(),
+            text: Vec::new(),
+            rng: StdRng::from_entropy(),
+        }
+    }
 }
 
 #[derive(Default, Clone)]
@@ -30,23 +40,38 @@ enum SpecialCharMode {
 
 impl Animation for Matrix {
     fn name(&self) -> &'static str {
-        "The Matrix"
+        Matrix::NAME
     }
 
     fn author(&self) -> &'static str {
-        "Jo"
+        Matrix::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        if !self.initialized {
-            self.initialize(frame);
-            self.initialized = true;
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn init(&mut self, size: Size) {
+        self.initialize(size);
+    }
+
+    fn resize(&mut self, _old: Size, new: Size) {
+        // columns that are still visible keep falling
+        self.columns.truncate(new.width);
+        while self.columns.len() < new.width {
+            let mut column = Column::random(new.height, &mut self.rng);
+            column.special_char_mode = self.special_char_mode;
+            self.columns.push(column);
         }
 
+        self.place_text(new);
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
         let mut all_done = true;
 
         for (x, column) in self.columns.iter_mut().enumerate() {
-            column.render(frame, x);
+            column.render(frame, x, time.delta);
 
             all_done = all_done && column.done;
         }
@@ -58,12 +83,15 @@ impl Animation for Matrix {
             } else {
                 self.special_char_mode = SpecialCharMode::Appear;
             }
-            self.initialize(frame);
+            self.initialize(frame.size());
         }
     }
 }
 
 impl Matrix {
+    pub const NAME: &'static str = "The Matrix";
+    pub const AUTHOR: &'static str = "Jo";
+
     pub const TEXTE: &'static [&'static str] = &["Dynamic Host Configuration Protocol"];
     //pub const TEXTE: &'static [&'static str] = &[
     //    "Fanatischer Aalen Termin",
@@ -79,23 +107,22 @@ impl Matrix {
     //    "Frecher Aalen Termin",
     //];
 
-    pub fn get_text() -> Vec<char> {
-        let mut rng = rand::thread_rng();
-
+    pub fn get_text(rng: &mut impl Rng) -> Vec<char> {
         let funny = rng.gen();
 
         if funny {
             let text = Matrix::TEXTE[rng.gen_range(0..Matrix::TEXTE.len())];
-            return text.chars().collect();
+            text.chars().collect()
         } else {
-            return "Die Heilbronner Chaos Party".chars().collect();
+            "Die Heilbronner Chaos Party".chars().collect()
         }
     }
-    pub fn initialize(&mut self, frame: &Frame) {
+    pub fn initialize(&mut self, size: Size) {
         self.columns = vec![];
 
-        for _ in 0..frame.x {
-            self.columns.push(Column::random(frame.y));
+        for _ in 0..size.width {
+            self.columns
+                .push(Column::random(size.height, &mut self.rng));
         }
 
         for column in self.columns.iter_mut() {
@@ -103,17 +130,26 @@ impl Matrix {
         }
 
         if self.special_char_mode == SpecialCharMode::Appear {
-            self.text = Matrix::get_text();
+            self.text = Matrix::get_text(&mut self.rng);
+        }
+
+        self.place_text(size);
+    }
+
+    /// puts the text in the middle of the screen.
+    fn place_text(&mut self, size: Size) {
+        for column in self.columns.iter_mut() {
+            column.special_chars.clear();
         }
 
-        let offset = (frame.x - self.text.len()) / 2;
+        let offset = size.width.saturating_sub(self.text.len()) / 2;
 
-        let y = frame.y / 2;
+        let y = size.height / 2;
 
         for (index, character) in self.text.iter().enumerate() {
             let x = offset + index;
 
-            if x > frame.x {
+            if x >= size.width {
                 break;
             }
 
@@ -122,6 +158,7 @@ impl Matrix {
                 Character {
                     color: Color::WHITE.clone(),
                     character: *character,
+                    background: None,
                 },
             ));
         }
@@ -135,15 +172,14 @@ impl Column {
         b: 0.0,
     };
 
-    pub fn random(height: usize) -> Self {
-        let mut rng = rand::thread_rng();
-
+    pub fn random(height: usize, rng: &mut impl Rng) -> Self {
         let decay_factor: f32 = rng.gen::<f32>() / (height as f32 * 0.75) + 0.02; // decay between 0.05 and 0.1 => lines
                                                                                   // are 10 to 20 chars long
         let mut column = Column {
             chars: vec![],
             decay_factor,
-            speed: rng.gen::<f32>() / 1.5 + 0.5,
+            // cells per second
+            speed: (rng.gen::<f32>() / 1.5 + 0.5) * 60.0,
             position: rng.gen_range(-(height as f32)..0.0),
             done: false,
             special_chars: vec![],
@@ -156,10 +192,10 @@ impl Column {
 
         column
     }
-    pub fn render(&mut self, frame: &mut Frame, x: usize) {
+    pub fn render(&mut self, frame: &mut Frame, x: usize, delta: f32) {
         if !self.done {
             self.render_falling_chars(frame, x);
-            self.step(frame, x)
+            self.step(frame, x, delta)
         }
 
         // render the special chars
@@ -225,13 +261,14 @@ impl Column {
                 Character {
                     color: color.clone(),
                     character: characters[*char],
+                    background: None,
                 },
             );
             self.done = false;
         }
     }
 
-    pub fn step(&mut self, _frame: &mut Frame, _x: usize) {
-        self.position += self.speed;
+    pub fn step(&mut self, _frame: &mut Frame, _x: usize, delta: f32) {
+        self.position += self.speed * delta;
     }
 }
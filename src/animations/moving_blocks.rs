@@ -1,26 +1,57 @@
 use super::prelude::*;
 use crate::utils::fill_block;
 
-#[derive(Default)]
 pub struct MovingBlocks {
-    step: usize,
+    /// seconds since the start
+    time: f32,
+    block_size: usize,
 }
 
 impl MovingBlocks {
-    const CLOCK_DIVIDER: usize = 8;
-    const BLOCK_SIZE: usize = 3;
+    pub const NAME: &'static str = "Moving Blocks";
+    pub const AUTHOR: &'static str = "Jo";
+
+    /// cells the blocks move per second
+    const SPEED: f32 = 7.5;
+}
+
+impl Default for MovingBlocks {
+    fn default() -> Self {
+        MovingBlocks {
+            time: 0.0,
+            block_size: 3,
+        }
+    }
 }
 
 impl Animation for MovingBlocks {
     fn name(&self) -> &'static str {
-        "Moving Blocks"
+        MovingBlocks::NAME
     }
 
     fn author(&self) -> &'static str {
-        "Jo"
+        MovingBlocks::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![Parameter {
+            name: "block_size",
+            description: "width and height of the blocks",
+            kind: Kind::Integer { min: 1, max: 20 },
+            value: Value::Integer(self.block_size as i64),
+        }]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        if name == "block_size" {
+            self.block_size = value.as_integer().unwrap() as usize;
+        }
     }
 
-    fn render(&mut self, frame: &mut Frame) {
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.time += time.delta;
+        let position = (self.time * MovingBlocks::SPEED) as usize;
+
         // first set of blocks moving in x direction
         //
         let char_1 = Character {
@@ -30,27 +61,27 @@ impl Animation for MovingBlocks {
                 g: 0.839,
                 b: 0.0,
             },
+            background: None,
         };
 
         let mut y = 0;
         while y < frame.y {
-            let mut x = -(MovingBlocks::BLOCK_SIZE as isize)
-                + (self.step / MovingBlocks::CLOCK_DIVIDER + MovingBlocks::BLOCK_SIZE)
-                    .rem_euclid(4 * MovingBlocks::BLOCK_SIZE) as isize;
+            let mut x = -(self.block_size as isize)
+                + (position + self.block_size).rem_euclid(4 * self.block_size) as isize;
 
             while x <= frame.x as isize {
                 fill_block(
                     frame,
                     x,
                     y as isize,
-                    MovingBlocks::BLOCK_SIZE,
-                    MovingBlocks::BLOCK_SIZE,
+                    self.block_size,
+                    self.block_size,
                     char_1.clone(),
                 );
-                x += (4 * MovingBlocks::BLOCK_SIZE) as isize;
+                x += (4 * self.block_size) as isize;
             }
 
-            y += 4 * MovingBlocks::BLOCK_SIZE;
+            y += 4 * self.block_size;
         }
 
         let char_2 = Character {
@@ -60,28 +91,27 @@ impl Animation for MovingBlocks {
                 g: 0.550,
                 b: 1.0,
             },
+            background: None,
         };
 
-        let mut y = 2 * MovingBlocks::BLOCK_SIZE;
+        let mut y = 2 * self.block_size;
         while y < frame.y {
-            let mut x = -(MovingBlocks::BLOCK_SIZE as isize)
-                + (4 * MovingBlocks::BLOCK_SIZE as isize)
-                - (self.step / MovingBlocks::CLOCK_DIVIDER + MovingBlocks::BLOCK_SIZE)
-                    .rem_euclid(4 * MovingBlocks::BLOCK_SIZE) as isize;
+            let mut x = -(self.block_size as isize) + (4 * self.block_size as isize)
+                - (position + self.block_size).rem_euclid(4 * self.block_size) as isize;
 
             while x <= frame.x as isize {
                 fill_block(
                     frame,
                     x,
                     y as isize,
-                    MovingBlocks::BLOCK_SIZE,
-                    MovingBlocks::BLOCK_SIZE,
+                    self.block_size,
+                    self.block_size,
                     char_2.clone(),
                 );
-                x += (4 * MovingBlocks::BLOCK_SIZE) as isize;
+                x += (4 * self.block_size) as isize;
             }
 
-            y += 4 * MovingBlocks::BLOCK_SIZE;
+            y += 4 * self.block_size;
         }
 
         // second set of blocks moving in y direction
@@ -92,26 +122,26 @@ impl Animation for MovingBlocks {
                 g: 0.0,
                 b: 1.0,
             },
+            background: None,
         };
-        let mut x = 2 * MovingBlocks::BLOCK_SIZE;
+        let mut x = 2 * self.block_size;
         while x < frame.x {
-            let mut y = -(MovingBlocks::BLOCK_SIZE as isize)
-                + (self.step / MovingBlocks::CLOCK_DIVIDER + MovingBlocks::BLOCK_SIZE)
-                    .rem_euclid(4 * MovingBlocks::BLOCK_SIZE) as isize;
+            let mut y = -(self.block_size as isize)
+                + (position + self.block_size).rem_euclid(4 * self.block_size) as isize;
 
             while y <= frame.y as isize {
                 fill_block(
                     frame,
                     x as isize,
                     y,
-                    MovingBlocks::BLOCK_SIZE,
-                    MovingBlocks::BLOCK_SIZE,
+                    self.block_size,
+                    self.block_size,
                     char_3.clone(),
                 );
-                y += (4 * MovingBlocks::BLOCK_SIZE) as isize;
+                y += (4 * self.block_size) as isize;
             }
 
-            x += 4 * MovingBlocks::BLOCK_SIZE;
+            x += 4 * self.block_size;
         }
 
         // second set of blocks moving in y direction
@@ -122,30 +152,27 @@ impl Animation for MovingBlocks {
                 g: 0.77,
                 b: 0.12,
             },
+            background: None,
         };
 
         let mut x = 0;
         while x < frame.x {
-            let mut y = -(MovingBlocks::BLOCK_SIZE as isize)
-                + (4 * MovingBlocks::BLOCK_SIZE as isize)
-                - (self.step / MovingBlocks::CLOCK_DIVIDER + MovingBlocks::BLOCK_SIZE)
-                    .rem_euclid(4 * MovingBlocks::BLOCK_SIZE) as isize;
+            let mut y = -(self.block_size as isize) + (4 * self.block_size as isize)
+                - (position + self.block_size).rem_euclid(4 * self.block_size) as isize;
 
             while y <= frame.y as isize {
                 fill_block(
                     frame,
                     x as isize,
                     y,
-                    MovingBlocks::BLOCK_SIZE,
-                    MovingBlocks::BLOCK_SIZE,
+                    self.block_size,
+                    self.block_size,
                     char_4.clone(),
                 );
-                y += (4 * MovingBlocks::BLOCK_SIZE) as isize;
+                y += (4 * self.block_size) as isize;
             }
 
-            x += 4 * MovingBlocks::BLOCK_SIZE;
+            x += 4 * self.block_size;
         }
-
-        self.step += 1;
     }
 }
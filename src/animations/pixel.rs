@@ -6,6 +6,9 @@ pub struct Pixels {
 }
 
 impl Pixels {
+    pub const NAME: &'static str = "pixels";
+    pub const AUTHOR: &'static str = "Imarok";
+
     // Return value of image at coordinates or false if out of bounds.
     fn get_image_at(&self, x: usize, y: usize) -> bool {
         self.image.get((x, y)).copied().unwrap_or(false)
@@ -14,16 +17,16 @@ impl Pixels {
 
 impl Animation for Pixels {
     fn name(&self) -> &'static str {
-        "pixels"
+        Pixels::NAME
     }
 
     /// returns the author of the animation
     fn author(&self) -> &'static str {
-        "Imarok"
+        Pixels::AUTHOR
     }
 
     /// writes the next animation step into the given frame.
-    fn render(&mut self, frame: &mut Frame) {
+    fn render(&mut self, frame: &mut Frame, _time: &Time) {
         for (x, _) in self.image.row_iter().enumerate() {
             for (y, _) in self.image.column_iter().enumerate().step_by(2) {
                 let val = (self.get_image_at(x, y), self.get_image_at(x, y + 1));
@@ -33,17 +36,21 @@ impl Animation for Pixels {
                     (false, true) => '▄',
                     (true, true) => '█',
                 };
-                let frame_pixel =
-                    frame.get_mut(x + self.pos_top_left.0, y / 2 + self.pos_top_left.1);
-                *frame_pixel = Character {
-                    character,
-                    color: HSVColor {
-                        h: 0.0,
-                        s: 0.0,
-                        v: 0.4,
-                    }
-                    .into(),
-                };
+                // the image is cut off if the frame is too small
+                frame.set_at_clipping(
+                    (x + self.pos_top_left.0) as isize,
+                    (y / 2 + self.pos_top_left.1) as isize,
+                    Character {
+                        character,
+                        color: HSVColor {
+                            h: 0.0,
+                            s: 0.0,
+                            v: 0.4,
+                        }
+                        .into(),
+                        background: None,
+                    },
+                );
             }
         }
     }
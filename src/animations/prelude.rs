@@ -1,3 +1,9 @@
 //! This prelude re-exports anything that is usually needed to create a new animation.
 pub use super::Animation;
-pub use crate::frame::{value_to_char, Character, Color, Frame, HSVColor};
+pub use crate::canvas::{Canvas, Resolution, Surface};
+pub use crate::clock::Time;
+pub use crate::frame::{value_to_char, Character, Color, Frame, HSVColor, Size};
+pub use crate::input::{Button, Event, Mouse, MouseKind};
+pub use crate::parameters::{Kind, Parameter, Value};
+pub use rand::rngs::StdRng;
+pub use rand::{Rng, SeedableRng};
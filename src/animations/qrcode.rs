@@ -7,21 +7,24 @@ pub struct QrCode {
 
 impl Animation for QrCode {
     fn name(&self) -> &'static str {
-        "QR Code"
+        QrCode::NAME
     }
 
     /// returns the author of the animation
     fn author(&self) -> &'static str {
-        "Imarok"
+        QrCode::AUTHOR
     }
 
     /// writes the next animation step into the given frame.
-    fn render(&mut self, frame: &mut Frame) {
-        self.pixels.render(frame)
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.pixels.render(frame, time)
     }
 }
 
 impl QrCode {
+    pub const NAME: &'static str = "QR Code";
+    pub const AUTHOR: &'static str = "Imarok";
+
     pub fn new(text: &str, pos_top_left: (usize, usize)) -> Self {
         let pixels = qrcode::QrCode::new(text).unwrap().to_colors();
         use num_integer::Roots;
@@ -5,8 +5,10 @@ pub struct Rainbow {
     rotation: f32,
 }
 impl Rainbow {
-    const NAME: &'static str = "Rainbow";
-    const AUTHOR: &'static str = "Jo";
+    pub const NAME: &'static str = "Rainbow";
+    pub const AUTHOR: &'static str = "Jo";
+    /// hue shift and rotation per second
+    const SPEED: f32 = 0.6;
 }
 
 impl Default for Rainbow {
@@ -27,9 +29,9 @@ impl Animation for Rainbow {
         Rainbow::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        self.color_shift += 0.01;
-        self.rotation += 0.01;
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.color_shift += Rainbow::SPEED * time.delta;
+        self.rotation += Rainbow::SPEED * time.delta;
         for y in 0..frame.y {
             for x in 0..frame.x {
                 // translate coordinates to [0, 1]
@@ -69,7 +71,11 @@ impl Animation for Rainbow {
                     _ => '4',
                 };
 
-                frame.data[y * frame.x + x] = Character { character, color };
+                frame.data[y * frame.x + x] = Character {
+                    character,
+                    color,
+                    background: None,
+                };
             }
         }
     }
@@ -1,5 +1,4 @@
 use super::prelude::*;
-use rand::Rng;
 
 #[derive(Debug, Clone)]
 struct RandomWalker {
@@ -9,18 +8,16 @@ struct RandomWalker {
 }
 
 impl RandomWalker {
-    fn random() -> RandomWalker {
-        let mut rng = rand::thread_rng();
-
+    fn random(rng: &mut impl Rng) -> RandomWalker {
         RandomWalker {
             x: rng.gen_range(0..1024),
             y: rng.gen_range(0..1024),
-            character: Character::random(),
+            character: Character::random(rng),
         }
     }
 
-    fn walk(&mut self, frame: &Frame) {
-        let direction = rand::thread_rng().gen_range(0..4);
+    fn walk(&mut self, frame: &Frame, rng: &mut impl Rng) {
+        let direction = rng.gen_range(0..4);
 
         // dbg!(direction, self.x, self.y);
 
@@ -38,24 +35,33 @@ impl RandomWalker {
 
 pub struct RandomWalkers {
     walkers: Vec<RandomWalker>,
+    /// seconds not yet spent on steps
+    pending: f32,
+    rng: StdRng,
 }
 
 impl Default for RandomWalkers {
     fn default() -> RandomWalkers {
-        let mut walkers = RandomWalkers {
-            walkers: Vec::new(),
-        };
-
-        for _ in 0..10 {
-            walkers.walkers.push(RandomWalker::random())
-        }
-
-        walkers
+        RandomWalkers::with_rng(StdRng::from_entropy())
     }
 }
 impl RandomWalkers {
-    const NAME: &'static str = "RandomWalkers";
-    const AUTHOR: &'static str = "Jo";
+    pub const NAME: &'static str = "RandomWalkers";
+    pub const AUTHOR: &'static str = "Jo";
+    /// steps per second
+    const SPEED: f32 = 60.0;
+    /// the most seconds caught up on at once, after a pause the walkers just continue
+    const MAX_DELTA: f32 = 1.0;
+
+    fn with_rng(mut rng: StdRng) -> RandomWalkers {
+        let walkers = (0..10).map(|_| RandomWalker::random(&mut rng)).collect();
+
+        RandomWalkers {
+            walkers,
+            pending: 0.0,
+            rng,
+        }
+    }
 }
 
 impl Animation for RandomWalkers {
@@ -67,14 +73,59 @@ impl Animation for RandomWalkers {
         RandomWalkers::AUTHOR
     }
 
-    /// writes the next animation step into the given frame.
-    fn render(&mut self, frame: &mut Frame) {
+    fn seed(&mut self, seed: u64) {
+        *self = RandomWalkers::with_rng(StdRng::seed_from_u64(seed));
+    }
+
+    /// moves all walkers into the frame, this is also used on resizes.
+    fn init(&mut self, size: Size) {
         for walker in self.walkers.iter_mut() {
-            walker.walk(frame);
+            walker.x %= size.width;
+            walker.y %= size.height;
+        }
+    }
+
+    /// writes the next animation step into the given frame.
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.pending += time.delta.min(RandomWalkers::MAX_DELTA) * RandomWalkers::SPEED;
+
+        while self.pending >= 1.0 {
+            self.pending -= 1.0;
+
+            for walker in self.walkers.iter_mut() {
+                walker.walk(frame, &mut self.rng);
 
-            //dbg!(walker.clone());
+                //dbg!(walker.clone());
 
+                *frame.get_mut(walker.x, walker.y) = walker.character.clone();
+            }
+        }
+
+        for walker in self.walkers.iter() {
             *frame.get_mut(walker.x, walker.y) = walker.character.clone();
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_long_frame() {
+        let mut walkers = RandomWalkers::with_rng(StdRng::seed_from_u64(1));
+        let mut frame = Frame::new(20, 10);
+        walkers.init(frame.size());
+
+        // a pause does not turn into a burst of steps
+        walkers.render(
+            &mut frame,
+            &Time {
+                elapsed: 3600.0,
+                delta: f32::INFINITY,
+                frame: 1,
+            },
+        );
+        assert!(walkers.pending < 1.0);
+    }
+}
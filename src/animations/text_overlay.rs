@@ -1,24 +1,25 @@
 use super::prelude::*;
+use crate::layers::Layer;
 
 pub struct TextOverlay {
     pub text: String,
 }
 
 impl TextOverlay {
-    const NAME: &'static str = "TextOverlay";
-    const AUTHOR: &'static str = "Jo";
-}
-
-impl Animation for TextOverlay {
-    fn name(&self) -> &'static str {
-        TextOverlay::NAME
-    }
+    pub const NAME: &'static str = "TextOverlay";
+    pub const AUTHOR: &'static str = "Jo";
 
-    fn author(&self) -> &'static str {
-        TextOverlay::AUTHOR
+    /// Returns a layer with the text, everything else is transparent.
+    pub fn layer(&self, size: Size) -> Layer {
+        let mut layer = Layer::new(size);
+        self.place(size, |x, y, character| {
+            layer.set_at_clipping(x as isize, y as isize, character)
+        });
+        layer
     }
 
-    fn render(&mut self, frame: &mut Frame) {
+    /// Calls `draw` with the position of every character of the text.
+    fn place(&self, size: Size, mut draw: impl FnMut(usize, usize, Character)) {
         let mut line = 0;
         let mut char_in_line = 0;
         for character in self.text.chars() {
@@ -30,23 +31,44 @@ impl Animation for TextOverlay {
             }
 
             // handling of text wrapping
-            if char_in_line >= frame.x {
+            if char_in_line >= size.width {
                 line += 1;
                 char_in_line = 0;
             }
 
             // return if the screen is full
-            if line >= frame.y {
+            if line >= size.height {
                 return;
             }
 
             // actually place the character
-            frame.data[line * frame.x + char_in_line] = Character {
-                character,
-                color: Color::white(),
-            };
+            draw(
+                char_in_line,
+                line,
+                Character {
+                    character,
+                    color: Color::white(),
+                    background: None,
+                },
+            );
 
             char_in_line += 1;
         }
     }
 }
+
+impl Animation for TextOverlay {
+    fn name(&self) -> &'static str {
+        TextOverlay::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        TextOverlay::AUTHOR
+    }
+
+    fn render(&mut self, frame: &mut Frame, _time: &Time) {
+        self.place(frame.size(), |x, y, character| {
+            frame.set_at(x, y, character)
+        });
+    }
+}
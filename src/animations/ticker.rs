@@ -0,0 +1,66 @@
+use super::prelude::*;
+
+/// Text that scrolls through the bottom line of the frame from right to left.
+pub struct Ticker {
+    pub text: String,
+    /// cells per second
+    speed: f32,
+    /// how far the text scrolled in cells
+    offset: f32,
+}
+
+impl Ticker {
+    pub const NAME: &'static str = "Ticker";
+    pub const AUTHOR: &'static str = "Jo";
+
+    /// space between the end of the text and its next repetition
+    const GAP: usize = 5;
+
+    pub fn new(text: String) -> Ticker {
+        Ticker {
+            text,
+            speed: 10.0,
+            offset: 0.0,
+        }
+    }
+}
+
+impl Animation for Ticker {
+    fn name(&self) -> &'static str {
+        Ticker::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        Ticker::AUTHOR
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        let characters: Vec<char> = self.text.chars().collect();
+        if characters.is_empty() || frame.y == 0 {
+            return;
+        }
+
+        // the text starts at the right edge and repeats after the gap
+        let length = characters.len() + Ticker::GAP;
+        let start = self.offset as usize;
+
+        for x in 0..frame.x {
+            let Some(index) = (start + x).checked_sub(frame.x) else {
+                continue;
+            };
+            if let Some(&character) = characters.get(index % length) {
+                frame.set_at(
+                    x,
+                    frame.y - 1,
+                    Character {
+                        character,
+                        color: Color::white(),
+                        background: None,
+                    },
+                );
+            }
+        }
+
+        self.offset += self.speed * time.delta;
+    }
+}
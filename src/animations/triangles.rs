@@ -5,41 +5,78 @@ use nalgebra::Vector2;
 
 #[derive(Default)]
 pub struct Triangles {
-    step: usize,
+    /// rotation in radians
+    angle: f32,
+    resolution: Resolution,
+}
+
+impl Triangles {
+    pub const NAME: &'static str = "Triangles";
+    pub const AUTHOR: &'static str = "Jo";
+
+    /// rotation speed in radians per second
+    const SPEED: f32 = std::f32::consts::PI / 6.0;
 }
 
 impl Animation for Triangles {
     fn name(&self) -> &'static str {
-        "Triangles"
+        Triangles::NAME
     }
 
     fn author(&self) -> &'static str {
-        "Jo"
+        Triangles::AUTHOR
     }
 
-    fn render(&mut self, frame: &mut Frame) {
-        self.step += 1;
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![Parameter {
+            name: "resolution",
+            description: "pixels per character",
+            kind: Kind::Choice(Resolution::NAMES),
+            value: Value::Text(self.resolution.name().to_string()),
+        }]
+    }
 
-        let radius= (frame.x / 2).min(frame.y) as f32;
-        let center = Vector2::new((frame.x/2) as f32, (frame.y/2) as f32);
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        if name == "resolution" {
+            self.resolution = value.as_text().unwrap().parse().unwrap();
+        }
+    }
 
-        let angle1 = std::f32::consts::PI * self.step as f32 / 360.0;
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        self.angle += Triangles::SPEED * time.delta;
+
+        let radius = (frame.x / 2).min(frame.y) as f32;
+        let center = Vector2::new((frame.x / 2) as f32, (frame.y / 2) as f32);
+
+        let angle1 = self.angle;
         let angle2 = angle1 + (2.0 * std::f32::consts::PI / 3.0);
         let angle3 = angle2 + (2.0 * std::f32::consts::PI / 3.0);
 
-        let p0 = Vector2::new(angle1.sin(), angle1.cos()/2.0) * radius + center;
-        let p1 = Vector2::new(angle2.sin(), angle2.cos()/2.0) * radius + center;
-        let p2 = Vector2::new(angle3.sin(), angle3.cos()/2.0) * radius + center;
-
-        let character1 = Character { color: Color { r: 1.0, g: 0.0, b: 0.0 }, character: 'a' };
-        let character2 = Character { color: Color { r: 0.0, g: 1.0, b: 0.0 }, character: 'b' };
-        let character3 = Character { color: Color { r: 0.0, g: 0.0, b: 1.0 }, character: 'c' };
+        // the corners in pixels of the canvas
+        let (scale_x, scale_y) = self.resolution.scale();
+        let scale = Vector2::new(scale_x as f32, scale_y as f32);
+        let p0 = (Vector2::new(angle1.sin(), angle1.cos() / 2.0) * radius + center)
+            .component_mul(&scale);
+        let p1 = (Vector2::new(angle2.sin(), angle2.cos() / 2.0) * radius + center)
+            .component_mul(&scale);
+        let p2 = (Vector2::new(angle3.sin(), angle3.cos() / 2.0) * radius + center)
+            .component_mul(&scale);
 
+        let character1 = Character {
+            color: Color {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+            character: 'a',
+            background: None,
+        };
         let color_func = |_, _| character1.clone();
 
-        plot_line(frame, p0, p1, &color_func);
-        plot_line(frame, p1, p2, &color_func);
-        plot_line(frame, p2, p0, &color_func);
-
+        let mut canvas = Canvas::new(frame.size(), self.resolution);
+        plot_line(&mut canvas, p0, p1, &color_func);
+        plot_line(&mut canvas, p1, p2, &color_func);
+        plot_line(&mut canvas, p2, p0, &color_func);
+        canvas.draw(frame);
     }
 }
@@ -0,0 +1,245 @@
+use super::prelude::*;
+use crate::automaton::{Generations, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    /// the front of an electron
+    Head,
+    /// the back of an electron, so it keeps its direction
+    Tail,
+    Conductor,
+}
+
+/// Wireworld, electrons running around on loops of wire.
+pub struct Wireworld {
+    cells: Grid<Cell>,
+    generations: Generations,
+    /// the number of loops of wire at the start
+    loops: usize,
+    /// the number of electrons on every loop
+    electrons: usize,
+    rng: StdRng,
+}
+
+impl Wireworld {
+    pub const NAME: &'static str = "Wireworld";
+    pub const AUTHOR: &'static str = "Jo";
+
+    fn step(&mut self) {
+        self.cells.step(|cells, x, y| match cells.get(x, y) {
+            Cell::Empty => Cell::Empty,
+            Cell::Head => Cell::Tail,
+            Cell::Tail => Cell::Conductor,
+            Cell::Conductor => match cells.count(x, y, Cell::Head) {
+                1 | 2 => Cell::Head,
+                _ => Cell::Conductor,
+            },
+        });
+    }
+
+    /// lays out rectangular loops of wire with electrons on them.
+    fn initialize(&mut self) {
+        self.cells.fill(Cell::Empty);
+        let size = self.cells.size();
+        if size.width < 3 || size.height < 3 {
+            return;
+        }
+
+        // loops that would touch another one are left out, touching wires flood with electrons
+        const ATTEMPTS: usize = 10;
+        let mut placed = 0;
+        for _ in 0..self.loops * ATTEMPTS {
+            let width = self.rng.gen_range(3..=size.width.min(24));
+            let height = self.rng.gen_range(3..=size.height.min(12));
+            let left = self.rng.gen_range(0..=size.width - width);
+            let top = self.rng.gen_range(0..=size.height - height);
+
+            let touches = (top.saturating_sub(1)..(top + height + 1).min(size.height)).any(|y| {
+                (left.saturating_sub(1)..(left + width + 1).min(size.width))
+                    .any(|x| self.cells.get(x, y) != Cell::Empty)
+            });
+            if touches {
+                continue;
+            }
+
+            // the border clockwise, without the corners so electrons do not split up there
+            let (right, bottom) = (left + width - 1, top + height - 1);
+            let mut border = Vec::new();
+            border.extend((left + 1..right).map(|x| (x, top)));
+            border.extend((top + 1..bottom).map(|y| (right, y)));
+            border.extend((left + 1..right).rev().map(|x| (x, bottom)));
+            border.extend((top + 1..bottom).rev().map(|y| (left, y)));
+
+            for &(x, y) in border.iter() {
+                self.cells.set(x, y, Cell::Conductor);
+            }
+            for _ in 0..self.electrons {
+                let index = self.rng.gen_range(0..border.len());
+                let (x, y) = border[index];
+                self.cells.set(x, y, Cell::Head);
+                let (x, y) = border[(index + border.len() - 1) % border.len()];
+                self.cells.set(x, y, Cell::Tail);
+            }
+
+            placed += 1;
+            if placed == self.loops {
+                break;
+            }
+        }
+    }
+
+    fn draw(cell: Cell) -> Option<Character> {
+        let (character, color) = match cell {
+            Cell::Empty => return None,
+            Cell::Head => ('@', Color::white()),
+            Cell::Tail => (
+                'o',
+                Color {
+                    r: 0.3,
+                    g: 0.6,
+                    b: 1.0,
+                },
+            ),
+            Cell::Conductor => (
+                '.',
+                Color {
+                    r: 0.8,
+                    g: 0.45,
+                    b: 0.1,
+                },
+            ),
+        };
+
+        Some(Character {
+            character,
+            color,
+            background: None,
+        })
+    }
+}
+
+impl Default for Wireworld {
+    fn default() -> Wireworld {
+        Wireworld {
+            cells: Grid::new(Size::new(0, 0), Cell::Empty, false),
+            generations: Generations::new(12.0),
+            loops: 8,
+            electrons: 2,
+            rng: StdRng::from_entropy(),
+        }
+    }
+}
+
+impl Animation for Wireworld {
+    fn name(&self) -> &'static str {
+        Wireworld::NAME
+    }
+
+    fn author(&self) -> &'static str {
+        Wireworld::AUTHOR
+    }
+
+    fn parameters(&self) -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "speed",
+                description: "generations per second",
+                kind: Kind::Float {
+                    min: 0.1,
+                    max: 120.0,
+                },
+                value: Value::Float(self.generations.speed as f64),
+            },
+            Parameter {
+                name: "loops",
+                description: "loops of wire at the start",
+                kind: Kind::Integer { min: 1, max: 100 },
+                value: Value::Integer(self.loops as i64),
+            },
+            Parameter {
+                name: "electrons",
+                description: "electrons on every loop",
+                kind: Kind::Integer { min: 0, max: 10 },
+                value: Value::Integer(self.electrons as i64),
+            },
+        ]
+    }
+
+    fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    fn set_parameter(&mut self, name: &str, value: Value) {
+        match name {
+            "speed" => self.generations.speed = value.as_float().unwrap() as f32,
+            "loops" => self.loops = value.as_integer().unwrap() as usize,
+            "electrons" => self.electrons = value.as_integer().unwrap() as usize,
+            _ => (),
+        }
+    }
+
+    fn init(&mut self, size: Size) {
+        self.cells = Grid::new(size, Cell::Empty, false);
+        self.initialize();
+    }
+
+    fn handle_event(&mut self, event: &Event) {
+        let Event::Mouse(Mouse { kind, x, y }) = *event else {
+            return;
+        };
+        let size = self.cells.size();
+        if x >= size.width || y >= size.height {
+            return;
+        }
+
+        // a click on a wire starts an electron, anywhere else it lays wire, just like dragging
+        match (kind, self.cells.get(x, y)) {
+            (MouseKind::Press(Button::Left), Cell::Conductor) => self.cells.set(x, y, Cell::Head),
+            (MouseKind::Press(Button::Left) | MouseKind::Drag(Button::Left), Cell::Empty) => {
+                self.cells.set(x, y, Cell::Conductor)
+            }
+            _ => (),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, time: &Time) {
+        for _ in 0..self.generations.advance(time.delta) {
+            let previous = self.cells.clone();
+            self.step();
+            // no electrons are left, lay out new wires unless there were none to begin with
+            if self.cells == previous && self.electrons > 0 {
+                self.initialize();
+            }
+        }
+
+        self.cells.draw(frame, Wireworld::draw);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_electron() {
+        let mut wireworld = Wireworld {
+            cells: Grid::new(Size::new(5, 1), Cell::Conductor, false),
+            ..Wireworld::default()
+        };
+        wireworld.cells.set(0, 0, Cell::Tail);
+        wireworld.cells.set(1, 0, Cell::Head);
+
+        wireworld.step();
+        assert_eq!(
+            wireworld.cells.cells(),
+            [
+                Cell::Conductor,
+                Cell::Tail,
+                Cell::Head,
+                Cell::Conductor,
+                Cell::Conductor
+            ]
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! The parts that cellular automata have in common.
+//!
+//! A [`Grid`] stores one state per cell and computes the next generation from the neighborhood
+//! of every cell, with or without wrapping around the edges. [`Generations`] turns the time
+//! between two frames into the number of generations that are due.
+use crate::frame::{Character, Frame, Size};
+
+/// A grid of cells, one per character of the frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<S> {
+    width: usize,
+    height: usize,
+    cells: Vec<S>,
+    /// whether the edges wrap around, otherwise the neighbors outside are left out
+    pub wrap: bool,
+}
+
+impl<S: Copy + PartialEq> Grid<S> {
+    /// Creates a grid in which every cell has the given state.
+    pub fn new(size: Size, state: S, wrap: bool) -> Grid<S> {
+        Grid {
+            width: size.width,
+            height: size.height,
+            cells: vec![state; size.width * size.height],
+            wrap,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn cells(&self) -> &[S] {
+        &self.cells
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> S {
+        self.cells[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, state: S) {
+        self.cells[y * self.width + x] = state;
+    }
+
+    /// Sets every cell to the state.
+    pub fn fill(&mut self, state: S) {
+        self.cells.fill(state);
+    }
+
+    /// Sets every cell to the state returned for its position.
+    pub fn fill_with(&mut self, mut state: impl FnMut(usize, usize) -> S) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                self.cells[y * self.width + x] = state(x, y);
+            }
+        }
+    }
+
+    /// The position of the cell at the offset, `None` if it is outside and the grid does not wrap.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let x = x as isize + dx;
+        let y = y as isize + dy;
+
+        if self.wrap {
+            Some((
+                x.rem_euclid(self.width as isize) as usize,
+                y.rem_euclid(self.height as isize) as usize,
+            ))
+        } else if (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y) {
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+
+    /// The states of the eight surrounding cells (Moore neighborhood).
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = S> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+        self.states_at(x, y, &OFFSETS)
+    }
+
+    /// The states of the four cells next to the cell (von Neumann neighborhood).
+    pub fn orthogonal_neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = S> + '_ {
+        const OFFSETS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+        self.states_at(x, y, &OFFSETS)
+    }
+
+    fn states_at<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = S> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.offset(x, y, dx, dy))
+            .map(|(x, y)| self.get(x, y))
+    }
+
+    /// The number of surrounding cells (Moore neighborhood) in the state.
+    pub fn count(&self, x: usize, y: usize, state: S) -> usize {
+        self.neighbors(x, y)
+            .filter(|neighbor| *neighbor == state)
+            .count()
+    }
+
+    /// Computes the next generation, `rule` returns the next state of the cell at x, y from the
+    /// current generation.
+    pub fn step(&mut self, rule: impl Fn(&Grid<S>, usize, usize) -> S) {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                cells.push(rule(self, x, y));
+            }
+        }
+        self.cells = cells;
+    }
+
+    /// Changes the size and keeps the cells that are still inside, new cells get the state
+    /// returned for their position.
+    pub fn resize(&mut self, size: Size, mut state: impl FnMut(usize, usize) -> S) {
+        let mut cells = Vec::with_capacity(size.width * size.height);
+        for y in 0..size.height {
+            for x in 0..size.width {
+                if x < self.width && y < self.height {
+                    cells.push(self.get(x, y));
+                } else {
+                    cells.push(state(x, y));
+                }
+            }
+        }
+
+        self.width = size.width;
+        self.height = size.height;
+        self.cells = cells;
+    }
+
+    /// Draws the cells onto the frame, cells for which `draw` returns `None` are left as they are.
+    pub fn draw(&self, frame: &mut Frame, draw: impl Fn(S) -> Option<Character>) {
+        for y in 0..self.height.min(frame.y) {
+            for x in 0..self.width.min(frame.x) {
+                if let Some(character) = draw(self.get(x, y)) {
+                    frame.set_at(x, y, character);
+                }
+            }
+        }
+    }
+}
+
+/// Counts the generations that are due at a given speed.
+#[derive(Debug, Clone, Copy)]
+pub struct Generations {
+    /// generations per second
+    pub speed: f32,
+    /// generations that are due but not computed yet
+    pending: f32,
+}
+
+impl Generations {
+    /// the most seconds caught up on at once, after a pause the animation just continues
+    const MAX_DELTA: f32 = 1.0;
+
+    pub fn new(speed: f32) -> Generations {
+        Generations {
+            speed,
+            pending: 0.0,
+        }
+    }
+
+    /// Returns the number of generations that are due after `delta` seconds, at most one
+    /// second's worth.
+    pub fn advance(&mut self, delta: f32) -> usize {
+        self.pending += delta.min(Generations::MAX_DELTA) * self.speed;
+        let mut due = 0;
+        while self.pending >= 1.0 {
+            self.pending -= 1.0;
+            due += 1;
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let mut grid = Grid::new(Size::new(4, 3), 0, true);
+        grid.fill_with(|x, y| y * 4 + x);
+
+        let mut neighbors: Vec<usize> = grid.neighbors(0, 0).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, [1, 3, 4, 5, 7, 8, 9, 11]);
+
+        grid.wrap = false;
+        assert_eq!(grid.neighbors(0, 0).count(), 3);
+        assert_eq!(grid.orthogonal_neighbors(0, 0).collect::<Vec<_>>(), [1, 4]);
+        assert_eq!(grid.offset(3, 2, 1, 0), None);
+    }
+
+    #[test]
+    fn test_step() {
+        // every cell takes the state of its left neighbor
+        let mut grid = Grid::new(Size::new(3, 1), false, true);
+        grid.set(0, 0, true);
+        grid.step(|grid, x, y| grid.get((x + 2) % 3, y));
+        assert_eq!(grid.cells(), [false, true, false]);
+
+        grid.resize(Size::new(4, 2), |x, _| x == 3);
+        assert_eq!(grid.size(), Size::new(4, 2));
+        assert_eq!(
+            grid.cells(),
+            [false, true, false, true, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_generations() {
+        let mut generations = Generations::new(10.0);
+        assert_eq!(generations.advance(0.25), 2);
+        assert_eq!(generations.advance(0.1), 1);
+        assert_eq!(generations.advance(0.0), 0);
+
+        // a long pause does not lead to a burst of generations
+        assert_eq!(generations.advance(3600.0), 10);
+        assert_eq!(generations.advance(f32::INFINITY), 10);
+        assert_eq!(generations.advance(0.1), 1);
+    }
+}
@@ -0,0 +1,98 @@
+use asciimation::animations::{self, REGISTRY};
+use asciimation::frame::Size;
+use asciimation::parameters;
+use asciimation::raster::{GifSink, PngSequenceSink};
+use asciimation::runner::Runner;
+use asciimation::sink::Sink;
+use clap::Parser;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time;
+
+/// Renders an animation without a terminal and exports it as an animated GIF or as a sequence
+/// of PNG files.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+struct Args {
+    /// Name of the animation to export, see the --list option of shuffle
+    animation: String,
+
+    /// Output file, a GIF is written if it ends with .gif, otherwise it is a directory that is
+    /// filled with numbered PNG files
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Number of frames to render
+    #[arg(short, long, default_value_t = 120)]
+    frames: usize,
+
+    /// Frames per second of the export
+    #[arg(long, default_value_t = 60)]
+    fps: u32,
+
+    /// Width of the virtual terminal in characters
+    #[arg(long, default_value_t = 80)]
+    width: usize,
+
+    /// Height of the virtual terminal in characters
+    #[arg(long, default_value_t = 24)]
+    height: usize,
+
+    /// Seed for the random numbers, exports with the same seed and options are identical
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+
+    /// Set a parameter of the animation, e.g. speed=4. Can be given multiple times.
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    parameters: Vec<String>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let Some(entry) = animations::find(&args.animation) else {
+        eprintln!("Unknown animation '{}', available are:", args.animation);
+        for entry in REGISTRY.iter() {
+            eprintln!("  {}", entry.id);
+        }
+        std::process::exit(1);
+    };
+    let mut animation = entry.create_seeded(args.seed);
+
+    for parameter in args.parameters.iter() {
+        let result = parameters::split_assignment(parameter)
+            .and_then(|(name, value)| animation.set_parameter_from_str(name, value));
+
+        if let Err(error) = result {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    let is_gif = args
+        .output
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"));
+
+    let mut sink: Box<dyn Sink> = if is_gif {
+        let file = File::create(&args.output).expect("Error creating the GIF");
+        Box::new(GifSink::new(BufWriter::new(file)))
+    } else {
+        Box::new(PngSequenceSink::new(&args.output).expect("Error creating the directory"))
+    };
+
+    let step_length = time::Duration::from_secs(1) / args.fps.max(1);
+    let size = Size::new(args.width, args.height);
+    let mut runner = Runner::new(animation);
+
+    for index in 0..args.frames {
+        let now = step_length * index as u32;
+        let (frame, _) = runner.render(size, now);
+
+        sink.present(&frame, now).expect("Error writing the output");
+    }
+
+    runner.finish();
+    sink.finish().expect("Error writing the output");
+}
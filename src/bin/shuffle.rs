@@ -1,16 +1,28 @@
-use asciimation::animations::{
-    Animation, Drops, Hexagons, Hills, Mandelbrot, Matrix, MovingBlocks, QrCode, Rainbow,
-    TextOverlay, GOL,
-};
-use asciimation::filters::{fadeout, DVDLogo};
-use asciimation::frame::Frame;
+use asciimation::animations::{self, Animation, Entry, TextOverlay, Ticker, REGISTRY};
+use asciimation::clock::{Clock, Time};
+use asciimation::color_mode::ColorMode;
+use asciimation::filters::DVDLogo;
+use asciimation::frame::{Frame, Size};
+use asciimation::input::{Event, Input, Key, Mouse};
+use asciimation::layers::{Compositor, Layer};
+use asciimation::parameters::Assignment;
+use asciimation::playlist::{Playlist, PlaylistEntry};
+use asciimation::renderer::Renderer;
+use asciimation::runner::Runner;
+use asciimation::server::{self, Client, Server};
+use asciimation::sink::{AsciicastSink, Sink};
+use asciimation::terminal::{self, Session};
+use asciimation::transitions::Transition;
+use asciimation::viewport::{Layout, Viewport};
 use clap::Parser;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
 use std::thread;
 use std::time;
 
-use terminal_size::terminal_size;
-
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
@@ -18,116 +30,988 @@ struct Args {
     #[arg(short, long, default_value_t = false)]
     debug: bool,
 
-    /// Time in seconds each animation is shown
+    /// Time in seconds each animation is shown, unless the playlist says otherwise
     #[arg(short, long, default_value_t = 60)]
     animation_time: usize,
 
-    /// Time in seconds when the fade-out starts
+    /// Time in seconds the transition to the next animation takes, unless the playlist says
+    /// otherwise
     #[arg(short, long, default_value_t = 2)]
     fadeout_time: usize,
+
+    /// Transition between animations for playlist entries that do not set one:
+    /// fade, crossfade, wipe-horizontal, wipe-vertical, dissolve or matrix-rain.
+    /// Picked randomly if not given.
+    #[arg(short, long)]
+    transition: Option<Transition>,
+
+    /// How the screen is split: single, columns, rows, grid (four animations at once) or ticker
+    /// (one animation with a line of scrolling text)
+    #[arg(long, default_value_t = Layout::Single)]
+    layout: Layout,
+
+    /// Text of the ticker line, the name of the animation if not given
+    #[arg(long)]
+    ticker: Option<String>,
+
+    /// Frames per second, this changes the smoothness but not the speed of the animations
+    #[arg(long, default_value_t = 60.0, value_parser = parse_fps)]
+    fps: f64,
+
+    /// TOML or JSON playlist that sets the rotation
+    #[arg(short, long, conflicts_with_all = ["only", "include", "exclude"])]
+    playlist: Option<PathBuf>,
+
+    /// Color depth of the terminal: truecolor, 256, 16 or mono.
+    /// Detected from COLORTERM and TERM if not given.
+    #[arg(short, long)]
+    color_mode: Option<ColorMode>,
+
+    /// Record the show into an asciicast v2 file instead of showing it in the terminal
+    #[arg(short, long, requires = "duration")]
+    record: Option<PathBuf>,
+
+    /// Stop after this many seconds
+    #[arg(long)]
+    duration: Option<u64>,
+
+    /// Serve the show to telnet clients on this address, e.g. localhost:2323, instead of
+    /// showing it in the terminal. Every client gets its own show.
+    #[arg(long, value_name = "ADDRESS", conflicts_with = "record")]
+    serve: Option<String>,
+
+    /// All clients of the server watch the same show, at the size of the largest window
+    #[arg(long, requires = "serve")]
+    shared: bool,
+
+    /// Maximum number of clients of the server
+    #[arg(long, default_value_t = 8, requires = "serve")]
+    max_clients: usize,
+
+    /// Disconnect clients of the server that did not send anything for this many seconds
+    #[arg(long, value_name = "SECONDS", requires = "serve")]
+    idle_timeout: Option<u64>,
+
+    /// Seed for the random numbers, a recording with the same seed and options is identical
+    #[arg(short, long)]
+    seed: Option<u64>,
+
+    /// List all animations and exit
+    #[arg(short, long)]
+    list: bool,
+
+    /// Only show this animation
+    #[arg(short, long, conflicts_with_all = ["include", "exclude"])]
+    only: Option<String>,
+
+    /// Comma separated list of animations to show instead of the default rotation
+    #[arg(short, long, value_delimiter = ',')]
+    include: Vec<String>,
+
+    /// Comma separated list of animations to remove from the rotation
+    #[arg(short, long, value_delimiter = ',')]
+    exclude: Vec<String>,
+
+    /// Set a parameter of an animation, e.g. gol.speed=4, see --list for all parameters.
+    /// Can be given multiple times.
+    #[arg(long = "param", value_name = "ANIMATION.NAME=VALUE")]
+    parameters: Vec<Assignment>,
+}
+
+/// Parses a frame rate, which has to be between 0.1 and 240.
+fn parse_fps(text: &str) -> Result<f64, String> {
+    const MIN_FPS: f64 = 0.1;
+    const MAX_FPS: f64 = 240.0;
+
+    match text.parse::<f64>() {
+        Ok(fps) if (MIN_FPS..=MAX_FPS).contains(&fps) => Ok(fps),
+        _ => Err(format!(
+            "'{}' is not a number from {} to {}",
+            text, MIN_FPS, MAX_FPS
+        )),
+    }
+}
+
+/// Checks that all parameters exist and have valid values or exits.
+fn check_parameters(parameters: &[Assignment]) {
+    for assignment in parameters.iter() {
+        let entry = find_or_exit(&assignment.animation);
+
+        if let Err(error) =
+            (entry.create)().set_parameter_from_str(&assignment.name, &assignment.value)
+        {
+            eprintln!("{}: {}", entry.id, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Sets the parameters from the command line that belong to the animation.
+fn apply_parameters(entry: &Entry, animation: &mut dyn Animation, parameters: &[Assignment]) {
+    for assignment in parameters.iter() {
+        if entry.matches(&assignment.animation) {
+            let _ = animation.set_parameter_from_str(&assignment.name, &assignment.value);
+        }
+    }
+}
+
+/// An animation that is currently shown.
+struct Showing<'a> {
+    /// the index of the entry in the playlist
+    index: usize,
+    entry: &'a PlaylistEntry,
+    runner: Runner,
+    /// when the animation started in show time
+    start: time::Duration,
+    /// how long the animation is shown, shorter than the entry says if it asked to end
+    duration: time::Duration,
+    dvd: Option<DVDLogo>,
+}
+
+impl<'a> Showing<'a> {
+    fn new(
+        playlist: &'a Playlist,
+        index: usize,
+        start: time::Duration,
+        parameters: &[Assignment],
+        rng: &mut impl Rng,
+    ) -> Showing<'a> {
+        let entry = &playlist.entries[index];
+        let runner = Showing::create(entry, parameters, rng);
+        let draw_dvd = rng.gen::<f32>() < 0.03;
+
+        Showing {
+            index,
+            entry,
+            runner,
+            start,
+            duration: entry.duration,
+            dvd: draw_dvd.then(DVDLogo::default),
+        }
+    }
+
+    fn create(entry: &PlaylistEntry, parameters: &[Assignment], rng: &mut impl Rng) -> Runner {
+        // parameters from the command line take precedence over the playlist
+        let mut animation = entry.create(rng.gen());
+        apply_parameters(entry.animation, animation.as_mut(), parameters);
+
+        Runner::new(animation)
+    }
+
+    /// Starts the animation again with a new seed, the remaining time stays the same.
+    fn reseed(&mut self, parameters: &[Assignment], rng: &mut impl Rng) {
+        let runner = Showing::create(self.entry, parameters, rng);
+        std::mem::replace(&mut self.runner, runner).finish();
+    }
+
+    fn toggle_dvd(&mut self) {
+        self.dvd = match self.dvd {
+            Some(_) => None,
+            None => Some(DVDLogo::default()),
+        };
+    }
+
+    /// The time left until the animation ends.
+    fn remaining(&self, now: time::Duration) -> time::Duration {
+        (self.start + self.duration).saturating_sub(now)
+    }
+
+    /// Fades the animation out from now on, unless it ends earlier anyway.
+    fn end_early(&mut self, now: time::Duration) {
+        let shown = now.saturating_sub(self.start);
+        self.duration = self.duration.min(shown + self.entry.fadeout);
+    }
+
+    fn render(&mut self, width: usize, height: usize, now: time::Duration) -> (Frame, Time) {
+        let size = Size::new(width, height);
+        let (frame, time) = self.runner.render(size, now);
+
+        let Some(dvd) = &mut self.dvd else {
+            return (frame, time);
+        };
+
+        // the logo floats above the animation
+        dvd.step(&frame, time.delta);
+        let mut logo = Layer::new(size);
+        dvd.draw(&mut logo);
+
+        let mut compositor = Compositor::new();
+        compositor.push(Layer::opaque(frame));
+        compositor.push(logo);
+
+        (compositor.flatten(size), time)
+    }
+}
+
+const HELP: &str = "Keyboard controls
+
+n, Right   next animation
+p, Left    previous animation
+Space      pause/resume
++, Up      speed up
+-, Down    slow down
+r          restart the animation with a new seed
+d          toggle the debug overlay
+v          toggle the DVD logo
+h, ?       toggle this help
+q          quit
+
+The mouse is passed to the animation, try clicking
+in Drops, Game of Life and Mandelbrot.";
+
+/// What the keyboard controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Next,
+    Previous,
+    Pause,
+    SpeedUp,
+    SlowDown,
+    Reseed,
+    ToggleDebug,
+    ToggleDvd,
+    ToggleHelp,
+    Quit,
+}
+
+impl Command {
+    fn from_event(event: Event) -> Option<Command> {
+        match event {
+            Event::Key(key) => match key {
+                Key::Char('n') | Key::Right => Some(Command::Next),
+                Key::Char('p') | Key::Left => Some(Command::Previous),
+                Key::Char(' ') => Some(Command::Pause),
+                Key::Char('+') | Key::Char('=') | Key::Up => Some(Command::SpeedUp),
+                Key::Char('-') | Key::Down => Some(Command::SlowDown),
+                Key::Char('r') => Some(Command::Reseed),
+                Key::Char('d') => Some(Command::ToggleDebug),
+                Key::Char('v') => Some(Command::ToggleDvd),
+                Key::Char('h') | Key::Char('?') => Some(Command::ToggleHelp),
+                Key::Char('q') => Some(Command::Quit),
+                _ => None,
+            },
+            Event::Mouse(_) => None,
+        }
+    }
+}
+
+/// The state that is changed by the keyboard controls, apart from the animations.
+#[derive(Debug)]
+struct Controls {
+    paused: bool,
+    /// factor for the speed of the show
+    speed: f64,
+    debug: bool,
+    help: bool,
+}
+
+impl Default for Controls {
+    fn default() -> Controls {
+        Controls {
+            paused: false,
+            speed: 1.0,
+            debug: false,
+            help: false,
+        }
+    }
+}
+
+impl Controls {
+    const MIN_SPEED: f64 = 0.125;
+    const MAX_SPEED: f64 = 8.0;
+
+    fn apply(&mut self, command: Command) {
+        match command {
+            Command::Pause => self.paused = !self.paused,
+            Command::SpeedUp => self.speed = (self.speed * 2.0).min(Controls::MAX_SPEED),
+            Command::SlowDown => self.speed = (self.speed / 2.0).max(Controls::MIN_SPEED),
+            Command::ToggleDebug => self.debug = !self.debug,
+            Command::ToggleHelp => self.help = !self.help,
+            _ => (),
+        }
+    }
+
+    /// a line for the overlay if the show is not running normally
+    fn status(&self) -> String {
+        let mut status = String::new();
+
+        if self.paused {
+            status += "\nPaused";
+        }
+        if self.speed != 1.0 {
+            status += &format!("\nSpeed: {}x", self.speed);
+        }
+
+        status
+    }
+}
+
+/// Looks up the animation or exits with a list of the known animations.
+fn find_or_exit(name: &str) -> &'static Entry {
+    match animations::find(name) {
+        Some(entry) => entry,
+        None => {
+            eprintln!("Unknown animation '{}', see --list", name);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Builds the list of animations to show from the command line arguments.
+fn select_animations(args: &Args) -> Vec<&'static Entry> {
+    if let Some(name) = &args.only {
+        return vec![find_or_exit(name)];
+    }
+
+    let mut animations: Vec<&'static Entry> = if args.include.is_empty() {
+        REGISTRY.iter().filter(|entry| entry.in_rotation).collect()
+    } else {
+        args.include.iter().map(|name| find_or_exit(name)).collect()
+    };
+
+    for name in args.exclude.iter() {
+        let excluded = find_or_exit(name);
+        animations.retain(|entry| entry.id != excluded.id);
+    }
+
+    if animations.is_empty() {
+        eprintln!("No animations left to show");
+        std::process::exit(1);
+    }
+
+    animations
+}
+
+fn list_animations() {
+    // the columns are as wide as the longest id and name plus some space
+    let id_width = REGISTRY
+        .iter()
+        .map(|entry| entry.id.len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let name_width = REGISTRY
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    for entry in REGISTRY.iter() {
+        println!(
+            "{:<id_width$}{:<name_width$}by {}{}",
+            entry.id,
+            entry.name,
+            entry.author,
+            if entry.in_rotation {
+                ""
+            } else {
+                " (not in the default rotation)"
+            }
+        );
+
+        for parameter in (entry.create)().parameters() {
+            println!(
+                "    {}.{}={}: {}, {}",
+                entry.id, parameter.name, parameter.value, parameter.description, parameter.kind
+            );
+        }
+    }
+}
+
+/// The animations of one tile of the layout, one after another with transitions between them.
+struct Show<'a> {
+    playlist: &'a Playlist,
+    parameters: &'a [Assignment],
+    /// the transition for entries that do not set one, random if `None`
+    transition: Option<Transition>,
+    current: Showing<'a>,
+    /// the next animation and how it is blended in, while the transition runs
+    next: Option<(Showing<'a>, Transition)>,
+    /// the entries shown before the current one, for going back
+    history: Vec<usize>,
+}
+
+impl<'a> Show<'a> {
+    fn new(
+        playlist: &'a Playlist,
+        args: &'a Args,
+        now: time::Duration,
+        rng: &mut impl Rng,
+    ) -> Show<'a> {
+        let first = playlist.next(None, rng);
+
+        Show {
+            playlist,
+            parameters: &args.parameters,
+            transition: args.transition,
+            current: Showing::new(playlist, first, now, &args.parameters, rng),
+            next: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Switches to the next animation right away.
+    fn skip(&mut self, now: time::Duration, rng: &mut impl Rng) {
+        let following = match self.next.take() {
+            Some((showing, _)) => showing,
+            None => {
+                let index = self.playlist.next(Some(self.current.index), rng);
+                Showing::new(self.playlist, index, now, self.parameters, rng)
+            }
+        };
+        self.history.push(self.current.index);
+        std::mem::replace(&mut self.current, following)
+            .runner
+            .finish();
+    }
+
+    /// Goes back to the previous animation, if there was one.
+    fn back(&mut self, now: time::Duration, rng: &mut impl Rng) {
+        if let Some(index) = self.history.pop() {
+            if let Some((showing, _)) = self.next.take() {
+                showing.runner.finish();
+            }
+            let previous = Showing::new(self.playlist, index, now, self.parameters, rng);
+            std::mem::replace(&mut self.current, previous)
+                .runner
+                .finish();
+        }
+    }
+
+    /// Switches to the next animation when the current one is over and starts the transition
+    /// before that.
+    fn update(&mut self, now: time::Duration, rng: &mut impl Rng) {
+        if self.next.is_none() && self.current.runner.is_finished() {
+            self.current.end_early(now);
+        }
+
+        if self.current.remaining(now).is_zero() {
+            self.skip(now, rng);
+        }
+
+        if self.next.is_none() && self.current.remaining(now) < self.current.entry.fadeout {
+            let transition = self
+                .current
+                .entry
+                .transition
+                .or(self.transition)
+                .unwrap_or_else(|| Transition::random(rng));
+            let index = self.playlist.next(Some(self.current.index), rng);
+            let showing = Showing::new(self.playlist, index, now, self.parameters, rng);
+            self.next = Some((showing, transition));
+        }
+    }
+
+    fn render(&mut self, size: Size, now: time::Duration) -> Frame {
+        let (mut frame, _) = self.current.render(size.width, size.height, now);
+
+        if let Some((showing, transition)) = &mut self.next {
+            let (incoming, _) = showing.render(size.width, size.height, now);
+            let progress = 1.0
+                - self.current.remaining(now).as_secs_f32()
+                    / self.current.entry.fadeout.as_secs_f32();
+
+            transition.apply(&mut frame, &incoming, progress);
+        }
+
+        frame
+    }
+
+    fn finish(self) {
+        self.current.runner.finish();
+        if let Some((showing, _)) = self.next {
+            showing.runner.finish();
+        }
+    }
+}
+
+/// Where the show is presented and where its size and input come from.
+trait Screen: Sink {
+    fn size(&mut self) -> Size;
+
+    /// returns the next input event if there is one
+    fn poll(&mut self) -> Option<Event> {
+        None
+    }
+
+    /// the viewer is gone, the show ends
+    fn is_closed(&self) -> bool {
+        false
+    }
+}
+
+/// The terminal the program runs in.
+struct Terminal {
+    renderer: Renderer,
+    input: Option<Input>,
+}
+
+impl Sink for Terminal {
+    fn present(&mut self, frame: &Frame, time: time::Duration) -> io::Result<()> {
+        self.renderer.present(frame, time)
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        self.renderer.bytes_per_frame()
+    }
+}
+
+impl Screen for Terminal {
+    fn size(&mut self) -> Size {
+        terminal::size()
+    }
+
+    fn poll(&mut self) -> Option<Event> {
+        self.input.as_ref().and_then(Input::poll)
+    }
+}
+
+/// A recording has the size of the terminal at the start.
+struct Recording {
+    sink: AsciicastSink<BufWriter<File>>,
+    size: Size,
+}
+
+impl Sink for Recording {
+    fn present(&mut self, frame: &Frame, time: time::Duration) -> io::Result<()> {
+        self.sink.present(frame, time)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.sink.finish()
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        self.sink.bytes_per_frame()
+    }
+}
+
+impl Screen for Recording {
+    fn size(&mut self) -> Size {
+        self.size
+    }
+}
+
+impl Screen for Client {
+    fn size(&mut self) -> Size {
+        Client::size(self)
+    }
+
+    fn poll(&mut self) -> Option<Event> {
+        Client::poll(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        Client::is_closed(self)
+    }
+}
+
+/// All clients of a server that watch the same show.
+struct Viewers {
+    server: Server,
+    clients: Vec<Client>,
+}
+
+impl Viewers {
+    /// Cuts the middle part of the frame that fits into the window of the client.
+    fn crop(frame: &Frame, size: Size) -> Frame {
+        let width = size.width.min(frame.x);
+        let height = size.height.min(frame.y);
+        let left = (frame.x - width) / 2;
+        let top = (frame.y - height) / 2;
+
+        let mut cropped = Frame::new(width, height);
+        for y in 0..height {
+            let row = (top + y) * frame.x + left;
+            cropped.data[y * width..(y + 1) * width]
+                .clone_from_slice(&frame.data[row..row + width]);
+        }
+        cropped
+    }
+}
+
+impl Sink for Viewers {
+    fn present(&mut self, frame: &Frame, time: time::Duration) -> io::Result<()> {
+        for client in self.clients.iter_mut() {
+            let cropped = Viewers::crop(frame, client.size());
+            // a failing client is closed and removed in the next `poll()`
+            let _ = client.present(&cropped, time);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        for client in self.clients.iter_mut() {
+            let _ = client.finish();
+        }
+        Ok(())
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        self.clients.iter().map(Client::bytes_per_frame).sum()
+    }
+}
+
+impl Screen for Viewers {
+    /// the size of the largest window, so every client sees a full frame
+    fn size(&mut self) -> Size {
+        self.clients
+            .iter()
+            .map(Client::size)
+            .reduce(|a, b| Size::new(a.width.max(b.width), a.height.max(b.height)))
+            .unwrap_or(server::DEFAULT_SIZE)
+    }
+
+    fn poll(&mut self) -> Option<Event> {
+        loop {
+            match self.server.try_accept() {
+                Ok(Some(client)) => {
+                    eprintln!("{} connected", client.address());
+                    self.clients.push(client);
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    eprintln!("Error accepting a client: {}", error);
+                    break;
+                }
+            }
+        }
+
+        let mut event = None;
+        for client in self.clients.iter_mut() {
+            while let Some(next) = client.poll() {
+                // quitting only disconnects the client, the show goes on for the others
+                if Command::from_event(next) == Some(Command::Quit) {
+                    let _ = client.finish();
+                    client.close();
+                } else {
+                    event = Some(next);
+                    break;
+                }
+            }
+            if event.is_some() {
+                break;
+            }
+        }
+
+        self.clients.retain(|client| {
+            if client.is_closed() {
+                eprintln!("{} disconnected", client.address());
+            }
+            !client.is_closed()
+        });
+
+        event
+    }
 }
 
 fn main() {
     let args = Args::parse();
 
-    let animations: Vec<fn() -> Box<dyn Animation>> = vec![
-        || Box::<Hexagons>::default(),
-        || Box::<Drops>::default(),
-        || Box::<Hills>::default(),
-        || Box::<MovingBlocks>::default(),
-        || Box::<Rainbow>::default(),
-        || Box::<GOL>::default(),
-        || Box::new(QrCode::new("https://github.com/991jo/asciimation", (5, 6))),
-        || Box::<Matrix>::default(),
-        || Box::<Mandelbrot>::default(),
-    ];
-
-    let mut step_start = time::Instant::now();
-    let step_length = time::Duration::from_millis(16);
+    if args.list {
+        list_animations();
+        return;
+    }
+
+    check_parameters(&args.parameters);
 
     let animation_duration = time::Duration::from_secs(args.animation_time as u64);
     let fadeout_time = time::Duration::from_secs(args.fadeout_time as u64);
 
-    // handle exit via Ctrl+C/SIGINT
+    let playlist = match &args.playlist {
+        Some(path) => match Playlist::load(path, animation_duration, fadeout_time) {
+            Ok(playlist) => playlist,
+            Err(error) => {
+                eprintln!("Error in playlist {}: {}", path.display(), error);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            // otherwise the next transition starts as soon as an animation is shown, playlists
+            // check the durations of their entries themselves
+            if args.fadeout_time >= args.animation_time {
+                eprintln!(
+                    "The fadeout time of {}s has to be shorter than the animation time of {}s",
+                    args.fadeout_time, args.animation_time
+                );
+                std::process::exit(1);
+            }
+            Playlist::from_animations(&select_animations(&args), animation_duration, fadeout_time)
+        }
+    };
+
+    // handle exit via Ctrl+C/SIGINT, SIGTERM and SIGHUP
     ctrlc::set_handler({
         || {
-            // Show cursor again
-            print!("\x1B[?25h");
+            terminal::restore();
             std::process::exit(1);
         }
     })
     .expect("Error setting handler for Ctrl+C");
 
-    loop {
-        for animation_fn in animations.iter() {
-            let mut dvd = DVDLogo::default();
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
 
-            let draw_dvd = rand::thread_rng().gen::<f32>() < 0.03;
-            let mut animation = animation_fn();
+    if let Some(address) = &args.serve {
+        serve(&args, &playlist, address, rng);
+        return;
+    }
 
-            let animation_start = time::Instant::now();
+    if let Some(path) = &args.record {
+        // recordings are played back elsewhere, so the local terminal does not matter
+        let color_mode = args.color_mode.unwrap_or(ColorMode::TrueColor);
+        let file = File::create(path).expect("Error creating the recording");
+        let mut recording = Recording {
+            sink: AsciicastSink::with_color_mode(BufWriter::new(file), color_mode),
+            size: terminal::size(),
+        };
+        // a recording does not depend on a live terminal and is rendered as fast as possible
+        run(&args, &playlist, &mut rng, &mut recording, false).expect("Error writing the output");
+        return;
+    }
 
-            step_start = time::Instant::now();
+    let color_mode = args.color_mode.unwrap_or_else(ColorMode::detect);
+    let _session = Session::start().expect("Error setting up the terminal");
+    let mut terminal = Terminal {
+        renderer: Renderer::with_color_mode(color_mode),
+        input: Input::spawn(),
+    };
+    run(&args, &playlist, &mut rng, &mut terminal, true).expect("Error writing the output");
+}
 
-            while animation_start + animation_duration >= step_start {
-                let animation_time_remaining = (animation_start + animation_duration) - step_start;
-                let size = terminal_size();
-                let (width, height) = size.unwrap();
+/// Serves the show to telnet clients until the program is stopped.
+fn serve(args: &Args, playlist: &Playlist, address: &str, mut rng: StdRng) {
+    let config = server::Config {
+        max_clients: args.max_clients,
+        idle_timeout: args.idle_timeout.map(time::Duration::from_secs),
+        color_mode: args.color_mode.unwrap_or(ColorMode::TrueColor),
+    };
+    let server = match Server::bind(address, config) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("Error listening on {}: {}", address, error);
+            std::process::exit(1);
+        }
+    };
+    eprintln!(
+        "Serving on {}, connect with telnet",
+        server.local_addr().expect("Error reading the address")
+    );
 
-                // build a frame
-                let mut frame = Frame::new(width.0 as usize, height.0 as usize);
-                animation.render(&mut frame);
+    if args.shared {
+        let mut viewers = Viewers {
+            server,
+            clients: Vec::new(),
+        };
+        let _ = run(args, playlist, &mut rng, &mut viewers, true);
+        return;
+    }
 
-                let elapsed = step_start.elapsed();
+    thread::scope(|scope| loop {
+        let mut client = match server.accept() {
+            Ok(client) => client,
+            Err(error) => {
+                eprintln!("Error accepting a client: {}", error);
+                continue;
+            }
+        };
+        let address = client.address();
+        eprintln!("{} connected", address);
 
-                if draw_dvd {
-                    dvd.step(&frame);
-                    dvd.draw(&mut frame);
-                }
+        // the same seed gives every client the same show
+        let seed = match args.seed {
+            Some(seed) => seed,
+            None => rng.gen(),
+        };
+        scope.spawn(move || {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let _ = run(args, playlist, &mut rng, &mut client, true);
+            eprintln!("{} disconnected", address);
+        });
+    });
+}
 
-                // check for fade out
-                if animation_time_remaining < fadeout_time {
-                    let fade = animation_time_remaining.as_secs_f32() / fadeout_time.as_secs_f32();
+/// Shows the playlist on the screen until it is closed, the duration is over or the viewer quits.
+///
+/// Live shows follow the real time, otherwise every frame advances the show by one frame length.
+fn run(
+    args: &Args,
+    playlist: &Playlist,
+    rng: &mut StdRng,
+    screen: &mut dyn Screen,
+    live: bool,
+) -> io::Result<()> {
+    let step_length = time::Duration::from_secs_f64(1.0 / args.fps);
+    let total_duration = args.duration.map(time::Duration::from_secs);
 
-                    fadeout(&mut frame, fade)
+    // the time since the start of the show, this is virtual time when recording.
+    let mut now = time::Duration::ZERO;
+
+    // every tile of the layout has its own show
+    let mut shows: Vec<Show> = (0..args.layout.tile_count())
+        .map(|_| Show::new(playlist, args, now, rng))
+        .collect();
+    let mut ticker = Ticker::new(String::new());
+    let mut ticker_clock = Clock::new();
+    // the show time of the last ticker frame
+    let mut ticker_time = now;
+
+    let mut controls = Controls {
+        debug: args.debug,
+        ..Controls::default()
+    };
+    let mut last_step = time::Instant::now();
+    let mut quit = false;
+
+    loop {
+        let tiles = args.layout.tiles(screen.size());
+
+        while let Some(event) = screen.poll() {
+            // everything that is not a command goes to the animations
+            let Some(command) = Command::from_event(event) else {
+                match event {
+                    // the mouse goes to the tile below it, in the coordinates of the tile
+                    Event::Mouse(mouse) => {
+                        let tile = tiles
+                            .iter()
+                            .zip(shows.iter_mut())
+                            .find(|(rect, _)| rect.contains(mouse.x, mouse.y));
+                        if let Some((rect, show)) = tile {
+                            let mouse = Mouse {
+                                x: mouse.x - rect.x,
+                                y: mouse.y - rect.y,
+                                ..mouse
+                            };
+                            show.current.runner.handle_event(&Event::Mouse(mouse));
+                        }
+                    }
+                    Event::Key(_) => {
+                        for show in shows.iter_mut() {
+                            show.current.runner.handle_event(&event);
+                        }
+                    }
                 }
+                continue;
+            };
 
-                // insert an overlay
-                let mut overlay = TextOverlay {
-                    text: format!(
-                        "Resolution: {}, {}\nAnimation: {}\nBy: {}",
-                        width.0,
-                        height.0,
-                        animation.name(),
-                        animation.author(),
-                    ),
-                };
-
-                if args.debug {
-                    // insert an overlay
-                    overlay = TextOverlay {
-                        text: format!(
-                                  "Resolution: {}, {}\nAnimation: {}\nBy: {}\nRender Time:{}/{}µs\nTime remaining: {}s",
-                                  width.0,
-                                  height.0,
-                                  animation.name(),
-                                  animation.author(),
-                                  elapsed.as_micros(),
-                                  step_length.as_micros(),
-                                  animation_time_remaining.as_secs(),
-                              ),
-                    };
+            match command {
+                Command::Quit => quit = true,
+                Command::Next => shows.iter_mut().for_each(|show| show.skip(now, rng)),
+                Command::Previous => shows.iter_mut().for_each(|show| show.back(now, rng)),
+                Command::Reseed => {
+                    for show in shows.iter_mut() {
+                        show.current.reseed(&args.parameters, rng);
+                    }
                 }
+                Command::ToggleDvd => shows[0].current.toggle_dvd(),
+                command => controls.apply(command),
+            }
+        }
+
+        if quit
+            || screen.is_closed()
+            || total_duration.is_some_and(|total_duration| now > total_duration)
+        {
+            shows.into_iter().for_each(Show::finish);
+            return screen.finish();
+        }
 
-                overlay.render(&mut frame);
+        for show in shows.iter_mut() {
+            show.update(now, rng);
+        }
 
-                frame.render();
+        let step_start = time::Instant::now();
+        let size = screen.size();
+        let Size { width, height } = size;
 
-                if elapsed < step_length {
-                    let sleep_time = step_length - elapsed;
-                    thread::sleep(sleep_time);
-                }
-                step_start = time::Instant::now();
+        // build a frame
+        let mut frame = Frame::new(width, height);
+        for (rect, show) in args.layout.tiles(size).into_iter().zip(shows.iter_mut()) {
+            if rect.width == 0 || rect.height == 0 {
+                continue;
+            }
+
+            let mut viewport = Viewport::new(&mut frame, rect);
+            *viewport = show.render(rect.size(), now);
+        }
+
+        if let Some(rect) = args.layout.ticker(size) {
+            let animation = shows[0].current.runner.animation();
+            ticker.text = match &args.ticker {
+                Some(text) => text.clone(),
+                None => format!("{} by {}", animation.name(), animation.author()),
+            };
+
+            let time = ticker_clock.tick(now.saturating_sub(ticker_time));
+            ticker_time = now;
+            ticker.render(&mut Viewport::new(&mut frame, rect), &time);
+        }
+
+        let elapsed = step_start.elapsed();
+        let main = &shows[0];
+        let animation = main.current.runner.animation();
+        let animation_time_remaining = main.current.remaining(now);
+
+        // insert an overlay
+        let mut overlay = TextOverlay {
+            text: format!(
+                "Resolution: {}, {}\nAnimation: {}\nBy: {}",
+                width,
+                height,
+                animation.name(),
+                animation.author(),
+            ),
+        };
+
+        if controls.debug {
+            // insert an overlay
+            overlay = TextOverlay {
+                text: format!(
+                          "Resolution: {}, {}\nAnimation: {}\nBy: {}\nRender Time:{}/{}µs\nOutput: {} bytes/frame\nTime remaining: {}s",
+                          width,
+                          height,
+                          animation.name(),
+                          animation.author(),
+                          elapsed.as_micros(),
+                          step_length.as_micros(),
+                          screen.bytes_per_frame(),
+                          animation_time_remaining.as_secs(),
+                      ),
+            };
+        }
+
+        if let Some(text) = &main.current.entry.overlay {
+            overlay.text += "\n";
+            overlay.text += text;
+        }
+
+        overlay.text += &controls.status();
+
+        if controls.help {
+            overlay.text = HELP.to_string() + &controls.status();
+        }
+
+        overlay.layer(frame.size()).composite(&mut frame);
+
+        screen.present(&frame, now)?;
+
+        if !live {
+            now += step_length;
+        } else {
+            let elapsed = step_start.elapsed();
+            if elapsed < step_length {
+                let sleep_time = step_length - elapsed;
+                thread::sleep(sleep_time);
+            }
+
+            // the show time stands still while paused
+            let real_time = last_step.elapsed();
+            last_step = time::Instant::now();
+            if !controls.paused {
+                now += real_time.mul_f64(controls.speed);
             }
         }
     }
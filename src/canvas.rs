@@ -0,0 +1,305 @@
+//! Drawing at a finer resolution than one sample per character.
+//!
+//! A [`Canvas`] has several pixels per character of the frame it is drawn onto. Depending on the
+//! [`Resolution`], every character shows 1x2 pixels with half blocks, 2x2 pixels with quadrant
+//! blocks or 2x4 pixels with Braille dots. Everything that draws onto a [`Surface`], like
+//! `utils::sample` and `utils::plot_line`, can draw onto a canvas as well as onto a frame.
+use crate::frame::{Character, Color, Frame, Size};
+use std::fmt;
+use std::str::FromStr;
+
+/// Something that can be drawn on character by character.
+pub trait Surface {
+    /// the number of columns and rows that can be drawn on
+    fn size(&self) -> Size;
+
+    /// Sets the character at the position, nothing is done if it is outside of the surface.
+    fn set_at_clipping(&mut self, x: isize, y: isize, character: Character);
+}
+
+impl Surface for Frame {
+    fn size(&self) -> Size {
+        Frame::size(self)
+    }
+
+    fn set_at_clipping(&mut self, x: isize, y: isize, character: Character) {
+        Frame::set_at_clipping(self, x, y, character);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Resolution {
+    /// one pixel per character, which keeps the characters that were drawn
+    #[default]
+    Text,
+    /// 1x2 pixels per character
+    HalfBlock,
+    /// 2x2 pixels per character
+    Quadrant,
+    /// 2x4 pixels per character
+    Braille,
+}
+
+impl Resolution {
+    pub const ALL: [Resolution; 4] = [
+        Resolution::Text,
+        Resolution::HalfBlock,
+        Resolution::Quadrant,
+        Resolution::Braille,
+    ];
+
+    /// the names of all resolutions, in the order of `ALL`
+    pub const NAMES: &'static [&'static str] = &["text", "half", "quadrant", "braille"];
+
+    pub fn name(&self) -> &'static str {
+        Resolution::NAMES[*self as usize]
+    }
+
+    /// The number of pixels per character horizontally and vertically.
+    pub fn scale(&self) -> (usize, usize) {
+        match self {
+            Resolution::Text => (1, 1),
+            Resolution::HalfBlock => (1, 2),
+            Resolution::Quadrant => (2, 2),
+            Resolution::Braille => (2, 4),
+        }
+    }
+
+    /// The character that shows the pixels of a cell, bit `y * width + x` is set for every
+    /// pixel that is on.
+    fn glyph(&self, mask: u8) -> char {
+        const HALF_BLOCKS: [char; 4] = [' ', '▀', '▄', '█'];
+        const QUADRANTS: [char; 16] = [
+            ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+        ];
+        // the bits of the dots in the order of the pixels, see the Unicode Braille patterns
+        const BRAILLE_DOTS: [u32; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+
+        match self {
+            Resolution::Text => '█',
+            Resolution::HalfBlock => HALF_BLOCKS[mask as usize],
+            Resolution::Quadrant => QUADRANTS[mask as usize],
+            Resolution::Braille => {
+                let dots = (0..8)
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .map(|bit| BRAILLE_DOTS[bit])
+                    .sum::<u32>();
+                char::from_u32(0x2800 + dots).unwrap()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Resolution, String> {
+        Resolution::ALL
+            .into_iter()
+            .find(|resolution| resolution.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                format!(
+                    "unknown resolution '{}', use one of {}",
+                    text,
+                    Resolution::NAMES.join(", ")
+                )
+            })
+    }
+}
+
+/// Pixels that are drawn onto a frame.
+///
+/// A pixel is on if a character other than a space was set. The character itself is only shown
+/// in the text resolution, otherwise a cell shows which of its pixels are on in their average
+/// color. Half blocks show two different colors with the help of the background color.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    resolution: Resolution,
+    /// the size in pixels
+    size: Size,
+    pixels: Vec<Option<Character>>,
+}
+
+impl Canvas {
+    /// Creates an empty canvas that covers a frame of the given size.
+    pub fn new(cells: Size, resolution: Resolution) -> Canvas {
+        let (scale_x, scale_y) = resolution.scale();
+        let size = Size::new(cells.width * scale_x, cells.height * scale_y);
+
+        Canvas {
+            resolution,
+            size,
+            pixels: vec![None; size.width * size.height],
+        }
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Character> {
+        self.pixels[y * self.size.width + x].as_ref()
+    }
+
+    /// Draws the pixels onto the frame, starting in its top left corner.
+    pub fn draw(&self, frame: &mut Frame) {
+        let (scale_x, scale_y) = self.resolution.scale();
+        let width = (self.size.width / scale_x).min(frame.x);
+        let height = (self.size.height / scale_y).min(frame.y);
+
+        for y in 0..height {
+            for x in 0..width {
+                if self.resolution == Resolution::Text {
+                    if let Some(character) = self.get(x, y) {
+                        frame.set_at(x, y, character.clone());
+                    }
+                    continue;
+                }
+
+                let mut mask = 0;
+                let mut sum = Color::BLACK;
+                let mut count = 0;
+                for pixel_y in 0..scale_y {
+                    for pixel_x in 0..scale_x {
+                        let pixel = self.get(x * scale_x + pixel_x, y * scale_y + pixel_y);
+                        if let Some(pixel) = pixel.filter(|pixel| pixel.character != ' ') {
+                            mask |= 1 << (pixel_y * scale_x + pixel_x);
+                            sum.r += pixel.color.r;
+                            sum.g += pixel.color.g;
+                            sum.b += pixel.color.b;
+                            count += 1;
+                        }
+                    }
+                }
+
+                let character = if count == 0 {
+                    Character::empty()
+                } else if let Some(character) = self.two_colors(x, y, mask) {
+                    character
+                } else {
+                    sum.scale(1.0 / count as f32);
+                    Character {
+                        color: sum,
+                        character: self.resolution.glyph(mask),
+                        background: None,
+                    }
+                };
+                frame.set_at(x, y, character);
+            }
+        }
+    }
+
+    /// A half block cell with two differently colored pixels shows the upper one as the character
+    /// and the lower one as the background.
+    fn two_colors(&self, x: usize, y: usize, mask: u8) -> Option<Character> {
+        if self.resolution != Resolution::HalfBlock || mask != 0b11 {
+            return None;
+        }
+
+        let top = self.get(x, y * 2)?;
+        let bottom = self.get(x, y * 2 + 1)?;
+        (top.color != bottom.color).then(|| Character {
+            color: top.color.clone(),
+            character: '▀',
+            background: Some(bottom.color.clone()),
+        })
+    }
+}
+
+impl Surface for Canvas {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn set_at_clipping(&mut self, x: isize, y: isize, character: Character) {
+        if x >= 0 && (x as usize) < self.size.width && y >= 0 && (y as usize) < self.size.height {
+            self.pixels[y as usize * self.size.width + x as usize] = Some(character);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn on() -> Character {
+        Character {
+            color: Color::WHITE,
+            character: '@',
+            background: None,
+        }
+    }
+
+    fn draw(resolution: Resolution, pixels: &[(isize, isize)]) -> char {
+        let mut canvas = Canvas::new(Size::new(1, 1), resolution);
+        for &(x, y) in pixels {
+            canvas.set_at_clipping(x, y, on());
+        }
+
+        let mut frame = Frame::new(1, 1);
+        canvas.draw(&mut frame);
+        frame.get(0, 0).character
+    }
+
+    #[test]
+    fn test_glyphs() {
+        assert_eq!(draw(Resolution::Text, &[(0, 0)]), '@');
+        assert_eq!(draw(Resolution::HalfBlock, &[(0, 1)]), '▄');
+        assert_eq!(draw(Resolution::Quadrant, &[(1, 0), (0, 1)]), '▞');
+        assert_eq!(draw(Resolution::Braille, &[(0, 0), (1, 3)]), '⢁');
+        assert_eq!(draw(Resolution::Braille, &[]), ' ');
+        // outside of the canvas
+        assert_eq!(draw(Resolution::Braille, &[(2, 0), (0, 4)]), ' ');
+    }
+
+    #[test]
+    fn test_color() {
+        let mut canvas = Canvas::new(Size::new(1, 1), Resolution::HalfBlock);
+        canvas.set_at_clipping(0, 0, on());
+        canvas.set_at_clipping(
+            0,
+            1,
+            Character {
+                color: Color::BLACK,
+                character: '@',
+                background: None,
+            },
+        );
+
+        let mut frame = Frame::new(1, 1);
+        canvas.draw(&mut frame);
+        assert_eq!(frame.get(0, 0).character, '▀');
+        assert_eq!(frame.get(0, 0).color, Color::WHITE);
+        assert_eq!(frame.get(0, 0).background, Some(Color::BLACK));
+
+        let mut canvas = Canvas::new(Size::new(1, 1), Resolution::Quadrant);
+        canvas.set_at_clipping(0, 0, on());
+        canvas.set_at_clipping(
+            1,
+            1,
+            Character {
+                color: Color::BLACK,
+                character: '@',
+                background: None,
+            },
+        );
+
+        canvas.draw(&mut frame);
+        assert_eq!(frame.get(0, 0).character, '▚');
+        assert_eq!(frame.get(0, 0).color.r, 0.5);
+    }
+
+    #[test]
+    fn test_from_str() {
+        for resolution in Resolution::ALL {
+            assert_eq!(resolution.to_string().parse(), Ok(resolution));
+        }
+        assert!("sixel".parse::<Resolution>().is_err());
+    }
+}
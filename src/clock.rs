@@ -0,0 +1,65 @@
+//! Timing information for animations.
+//!
+//! Animations move based on the time that passed instead of the number of rendered frames, so
+//! they run at the same speed regardless of the frame rate.
+use std::time::Duration;
+
+/// The timing context an animation gets for every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Time {
+    /// seconds since the animation started
+    pub elapsed: f32,
+    /// seconds since the last frame
+    pub delta: f32,
+    /// number of the frame, starting with 0
+    pub frame: u64,
+}
+
+/// Keeps track of the time of an animation.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+    elapsed: Duration,
+    frame: u64,
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock::default()
+    }
+
+    /// Advances the clock by `delta` and returns the timing for the next frame.
+    ///
+    /// The first frame should be rendered with a delta of zero.
+    pub fn tick(&mut self, delta: Duration) -> Time {
+        self.elapsed += delta;
+
+        let time = Time {
+            elapsed: self.elapsed.as_secs_f32(),
+            delta: delta.as_secs_f32(),
+            frame: self.frame,
+        };
+        self.frame += 1;
+
+        time
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tick() {
+        let mut clock = Clock::new();
+
+        let time = clock.tick(Duration::ZERO);
+        assert_eq!(time.frame, 0);
+        assert_eq!(time.delta, 0.0);
+
+        clock.tick(Duration::from_millis(250));
+        let time = clock.tick(Duration::from_millis(500));
+        assert_eq!(time.frame, 2);
+        assert_eq!(time.delta, 0.5);
+        assert_eq!(time.elapsed, 0.75);
+    }
+}
@@ -0,0 +1,319 @@
+//! Output backends for terminals with different color depths.
+//!
+//! Not every terminal understands 24 bit colors. The Linux console, older tmux setups and serial
+//! terminals need the colors mapped onto the palette they support.
+use crate::frame::Color;
+use std::env;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24 bit colors
+    TrueColor,
+    /// the xterm 256 color palette
+    Ansi256,
+    /// the 16 standard ANSI colors
+    Ansi16,
+    /// no colors, only the luminance is mapped to dim/normal/bold text
+    Monochrome,
+}
+
+/// The terminal state needed to display a color in a given `ColorMode`.
+///
+/// Colors that map to the same pen look identical in the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Pen {
+    Rgb(u8, u8, u8),
+    Indexed(u8),
+    /// 0 is invisible, 1 is dim, 2 is normal and 3 is bold text.
+    Intensity(u8),
+}
+
+impl ColorMode {
+    /// Detects the color mode from the `COLORTERM` and `TERM` environment variables.
+    pub fn detect() -> ColorMode {
+        ColorMode::from_env(
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Guesses the color mode from the values of `COLORTERM` and `TERM`.
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> ColorMode {
+        if let Some("truecolor" | "24bit") = colorterm {
+            return ColorMode::TrueColor;
+        }
+
+        match term {
+            None | Some("" | "dumb") => ColorMode::Monochrome,
+            Some(term) if term.starts_with("vt") => ColorMode::Monochrome,
+            Some(term) if term.contains("direct") || term.contains("truecolor") => {
+                ColorMode::TrueColor
+            }
+            Some(term) if term.contains("256color") => ColorMode::Ansi256,
+            Some(_) => ColorMode::Ansi16,
+        }
+    }
+
+    /// A `TERM` value for a terminal with this color mode, `from_env` maps it back to the mode.
+    pub fn term(&self) -> &'static str {
+        match self {
+            ColorMode::TrueColor => "xterm-direct",
+            ColorMode::Ansi256 => "xterm-256color",
+            ColorMode::Ansi16 => "xterm",
+            ColorMode::Monochrome => "vt100",
+        }
+    }
+
+    /// Maps the color onto the palette of this mode.
+    pub fn pen(&self, color: &Color) -> Pen {
+        match self {
+            ColorMode::TrueColor => {
+                let (r, g, b) = color.to_rgb8();
+                Pen::Rgb(r, g, b)
+            }
+            ColorMode::Ansi256 => Pen::Indexed(nearest_256(color)),
+            ColorMode::Ansi16 => Pen::Indexed(nearest_16(color)),
+            ColorMode::Monochrome => {
+                let luminance = color.luminance();
+                let intensity = if luminance < 0.1 {
+                    0
+                } else if luminance < 0.35 {
+                    1
+                } else if luminance < 0.75 {
+                    2
+                } else {
+                    3
+                };
+                Pen::Intensity(intensity)
+            }
+        }
+    }
+
+    /// Maps a background color onto the palette of this mode, `None` is the default background.
+    pub fn background_pen(&self, color: Option<&Color>) -> Option<Pen> {
+        match self {
+            ColorMode::Monochrome => None,
+            _ => color.map(|color| self.pen(color)),
+        }
+    }
+}
+
+impl Pen {
+    /// Returns false if the character should not be drawn at all.
+    pub fn visible(&self) -> bool {
+        *self != Pen::Intensity(0)
+    }
+
+    /// Appends the escape sequence that selects this pen.
+    pub fn write_escape(&self, output: &mut String) {
+        let _ = match self {
+            Pen::Rgb(r, g, b) => write!(output, "\x1B[38;2;{};{};{}m", r, g, b),
+            Pen::Indexed(index @ 0..=7) => write!(output, "\x1B[{}m", 30 + index),
+            Pen::Indexed(index @ 8..=15) => write!(output, "\x1B[{}m", 90 + index - 8),
+            Pen::Indexed(index) => write!(output, "\x1B[38;5;{}m", index),
+            Pen::Intensity(0 | 2) => write!(output, "\x1B[22m"),
+            Pen::Intensity(1) => write!(output, "\x1B[22;2m"),
+            Pen::Intensity(_) => write!(output, "\x1B[22;1m"),
+        };
+    }
+
+    /// Appends the escape sequence that selects this pen as the background color, `None` is the
+    /// default background of the terminal.
+    ///
+    /// Monochrome terminals have no background colors, their pens always keep the default.
+    pub fn write_background_escape(pen: Option<Pen>, output: &mut String) {
+        let _ = match pen {
+            Some(Pen::Rgb(r, g, b)) => write!(output, "\x1B[48;2;{};{};{}m", r, g, b),
+            Some(Pen::Indexed(index @ 0..=7)) => write!(output, "\x1B[{}m", 40 + index),
+            Some(Pen::Indexed(index @ 8..=15)) => write!(output, "\x1B[{}m", 100 + index - 8),
+            Some(Pen::Indexed(index)) => write!(output, "\x1B[48;5;{}m", index),
+            None | Some(Pen::Intensity(_)) => write!(output, "\x1B[49m"),
+        };
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "truecolor" | "24bit" => Ok(ColorMode::TrueColor),
+            "256" | "ansi256" => Ok(ColorMode::Ansi256),
+            "16" | "ansi16" => Ok(ColorMode::Ansi16),
+            "mono" | "monochrome" => Ok(ColorMode::Monochrome),
+            _ => Err(format!(
+                "unknown color mode '{}', expected one of truecolor, 256, 16, mono",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorMode::TrueColor => "truecolor",
+            ColorMode::Ansi256 => "256",
+            ColorMode::Ansi16 => "16",
+            ColorMode::Monochrome => "mono",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// the xterm default values of the 16 standard colors
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// the channel values of the 6x6x6 color cube of the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    let r = a.0 as i32 - b.0 as i32;
+    let g = a.1 as i32 - b.1 as i32;
+    let b = a.2 as i32 - b.2 as i32;
+    r * r + g * g + b * b
+}
+
+fn nearest_16(color: &Color) -> u8 {
+    let rgb = color.to_rgb8();
+
+    (0..16)
+        .min_by_key(|index| distance(rgb, ANSI_16[*index as usize]))
+        .unwrap()
+}
+
+fn nearest_256(color: &Color) -> u8 {
+    let rgb = color.to_rgb8();
+
+    let nearest_level = |value: u8| {
+        (0..6)
+            .min_by_key(|index| (CUBE_LEVELS[*index] as i32 - value as i32).abs())
+            .unwrap()
+    };
+
+    // the closest color of the color cube
+    let (r, g, b) = (
+        nearest_level(rgb.0),
+        nearest_level(rgb.1),
+        nearest_level(rgb.2),
+    );
+    let cube_index = 16 + 36 * r + 6 * g + b;
+    let cube_color = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    // the closest color of the grayscale ramp
+    let average = (rgb.0 as usize + rgb.1 as usize + rgb.2 as usize) / 3;
+    // the ramp goes from 8 to 238 in steps of 10, round to the nearest step
+    let gray_step = ((average.saturating_sub(8) + 5) / 10).min(23);
+    let gray_value = (8 + 10 * gray_step) as u8;
+    let gray_index = 232 + gray_step;
+
+    if distance(rgb, (gray_value, gray_value, gray_value)) < distance(rgb, cube_color) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            ColorMode::from_env(Some("truecolor"), Some("xterm")),
+            ColorMode::TrueColor
+        );
+        assert_eq!(
+            ColorMode::from_env(None, Some("screen-256color")),
+            ColorMode::Ansi256
+        );
+        assert_eq!(ColorMode::from_env(None, Some("linux")), ColorMode::Ansi16);
+        assert_eq!(
+            ColorMode::from_env(None, Some("vt220")),
+            ColorMode::Monochrome
+        );
+        assert_eq!(ColorMode::from_env(None, None), ColorMode::Monochrome);
+    }
+
+    #[test]
+    fn test_palettes() {
+        let red = Color {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+        };
+        let gray = Color {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+
+        assert_eq!(ColorMode::Ansi256.pen(&red), Pen::Indexed(196));
+        assert_eq!(ColorMode::Ansi256.pen(&gray), Pen::Indexed(244));
+        assert_eq!(ColorMode::Ansi16.pen(&red), Pen::Indexed(9));
+        assert_eq!(ColorMode::Ansi16.pen(&Color::white()), Pen::Indexed(15));
+        assert_eq!(
+            ColorMode::Monochrome.pen(&Color::black()),
+            Pen::Intensity(0)
+        );
+        assert_eq!(
+            ColorMode::Monochrome.pen(&Color::white()),
+            Pen::Intensity(3)
+        );
+    }
+
+    #[test]
+    fn test_grays() {
+        // the color of an index of the cube or the grayscale ramp
+        let rgb = |index: u8| match index {
+            16..=231 => {
+                let index = index as usize - 16;
+                (
+                    CUBE_LEVELS[index / 36],
+                    CUBE_LEVELS[index / 6 % 6],
+                    CUBE_LEVELS[index % 6],
+                )
+            }
+            _ => {
+                let value = 8 + 10 * (index - 232);
+                (value, value, value)
+            }
+        };
+
+        for value in 0..=255u8 {
+            let gray = (value, value, value);
+            let color = Color {
+                r: value as f32 / 255.0,
+                g: value as f32 / 255.0,
+                b: value as f32 / 255.0,
+            };
+            let nearest = (16..=255).map(|index| distance(gray, rgb(index))).min();
+            assert_eq!(
+                Some(distance(gray, rgb(nearest_256(&color)))),
+                nearest,
+                "{}",
+                value
+            );
+        }
+    }
+}
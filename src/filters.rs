@@ -1,12 +1,13 @@
 use crate::frame::{Character, Color, Frame};
-use rand::seq::SliceRandom;
+use crate::layers::Layer;
 
 /// Fades the given frame to a blank screen.
 ///
 /// a fade value of 1.0 does not alter the frame, a value of 0.0 is completly blank.
 ///
 /// The screen is faded out by scaling down the colors with the given values and setting them to
-/// whitespace if they are below a certain luminance threshold.
+/// whitespace if they are below a certain luminance threshold. Background colors are scaled as
+/// well and fall back to the background of the terminal at 0.0.
 pub fn fadeout(frame: &mut Frame, fade: f32) {
     for char in frame.data.iter_mut() {
         char.color.scale(fade);
@@ -14,14 +15,24 @@ pub fn fadeout(frame: &mut Frame, fade: f32) {
         if char.color.luminance() < 0.1 {
             char.character = ' ';
         }
+
+        if let Some(background) = &mut char.background {
+            background.scale(fade);
+            // a completely faded background is the one of the terminal
+            if fade <= 0.0 {
+                char.background = None;
+            }
+        }
     }
 }
 
 pub struct DVDLogo {
-    x: isize,
-    y: isize,
-    x_speed: isize,
-    y_speed: isize,
+    x: f32,
+    y: f32,
+    /// cells per second
+    x_speed: f32,
+    /// cells per second
+    y_speed: f32,
 }
 
 impl DVDLogo {
@@ -38,46 +49,48 @@ impl DVDLogo {
      @@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@
                                                @";
 
-    const WIDTH: isize = 54;
-    const HEIGHT: isize = 12;
+    const WIDTH: f32 = 54.0;
+    const HEIGHT: f32 = 12.0;
+
+    /// moves the logo by `delta` seconds, bouncing off the edges of the frame.
+    pub fn step(&mut self, frame: &Frame, delta: f32) {
+        let x_step = self.x_speed * delta;
+        let y_step = self.y_speed * delta;
 
-    pub fn step(&mut self, frame: &Frame) {
         // x movement
-        if self.x_speed > 0 {
-            if (self.x + DVDLogo::WIDTH) as isize + self.x_speed >= frame.x as isize {
-                self.x_speed = -self.x_speed;
-            }
-        } else {
-            if self.x as isize + self.x_speed < 0 {
+        if self.x_speed > 0.0 {
+            if self.x + DVDLogo::WIDTH + x_step >= frame.x as f32 {
                 self.x_speed = -self.x_speed;
             }
+        } else if self.x + x_step < 0.0 {
+            self.x_speed = -self.x_speed;
         }
 
         // y movement
-        if self.y_speed > 0 {
-            if (self.y + DVDLogo::HEIGHT) as isize + self.y_speed >= frame.y as isize {
-                self.y_speed = -self.y_speed;
-            }
-        } else {
-            if self.y as isize + self.y_speed < 0 {
+        if self.y_speed > 0.0 {
+            if self.y + DVDLogo::HEIGHT + y_step >= frame.y as f32 {
                 self.y_speed = -self.y_speed;
             }
+        } else if self.y + y_step < 0.0 {
+            self.y_speed = -self.y_speed;
         }
 
-        self.x += self.x_speed;
-        self.y += self.y_speed;
+        self.x += self.x_speed * delta;
+        self.y += self.y_speed * delta;
     }
 
-    pub fn draw(&mut self, frame: &mut Frame) {
+    /// draws the logo onto a transparent layer, which can be put on top of an animation.
+    pub fn draw(&self, layer: &mut Layer) {
         for (y_index, line) in DVDLogo::LOGO.split("\n").enumerate() {
             for (x_index, character) in line.chars().enumerate() {
                 if character != ' ' {
-                    frame.set_at_clipping(
-                        self.x + x_index as isize,
-                        self.y + y_index as isize,
+                    layer.set_at_clipping(
+                        self.x as isize + x_index as isize,
+                        self.y as isize + y_index as isize,
                         Character {
                             color: Color::WHITE,
                             character,
+                            background: None,
                         },
                     );
                 }
@@ -89,10 +102,10 @@ impl DVDLogo {
 impl Default for DVDLogo {
     fn default() -> Self {
         DVDLogo {
-            x: 0,
-            y: 0,
-            x_speed: 2,
-            y_speed: 1,
+            x: 0.0,
+            y: 0.0,
+            x_speed: 120.0,
+            y_speed: 60.0,
         }
     }
 }
@@ -1,5 +1,7 @@
 use rand::prelude::*;
+use std::fmt;
 use std::io::{self, Write};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Color {
@@ -12,6 +14,21 @@ pub struct Color {
 pub struct Character {
     pub color: Color,
     pub character: char,
+    /// the color behind the character, `None` leaves the background of the terminal
+    pub background: Option<Color>,
+}
+
+/// The size of a frame in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Size {
+    pub fn new(width: usize, height: usize) -> Size {
+        Size { width, height }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -64,6 +81,15 @@ impl Color {
         0.3 * self.r + 0.59 * self.g + 0.11 * self.b
     }
 
+    /// Returns the color as 8 bit values per channel, as they are sent to the terminal.
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        (
+            (self.r * 256.0) as u8,
+            (self.g * 256.0) as u8,
+            (self.b * 256.0) as u8,
+        )
+    }
+
     /// interpolate between self and the other color.
     /// if value is clamped to 0.0 and 1.0.
     ///
@@ -78,6 +104,78 @@ impl Color {
     }
 }
 
+impl fmt::Display for Color {
+    /// writes the color as `#rrggbb`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (r, g, b) = self.to_rgb8();
+        write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parses `#rrggbb`, the `#` is optional.
+    fn from_str(text: &str) -> Result<Color, String> {
+        let text = text.trim();
+        let hex = text.strip_prefix('#').unwrap_or(text);
+        let channel = |index: usize| -> Option<f32> {
+            let digits = hex.get(index * 2..index * 2 + 2)?;
+            Some(u8::from_str_radix(digits, 16).ok()? as f32 / 255.0)
+        };
+
+        match (hex.len(), channel(0), channel(1), channel(2)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Color { r, g, b }),
+            _ => Err(format!("'{}' is not a color like #ff8000", text)),
+        }
+    }
+}
+
+/// Colors that blend into each other, the stops are spread evenly from 0.0 to 1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<Color>,
+}
+
+impl Gradient {
+    /// A gradient needs at least one color.
+    pub fn new(stops: Vec<Color>) -> Option<Gradient> {
+        (!stops.is_empty()).then_some(Gradient { stops })
+    }
+
+    /// Returns the color at the position, which is clamped between 0.0 and 1.0.
+    pub fn at(&self, position: f32) -> Color {
+        let position = position.clamp(0.0, 1.0) * (self.stops.len() - 1) as f32;
+        let index = (position as usize).min(self.stops.len() - 1);
+
+        match self.stops.get(index + 1) {
+            Some(next) => self.stops[index].interpolate(next, position - index as f32),
+            None => self.stops[index].clone(),
+        }
+    }
+}
+
+impl fmt::Display for Gradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stops: Vec<String> = self.stops.iter().map(Color::to_string).collect();
+        write!(f, "{}", stops.join(","))
+    }
+}
+
+impl FromStr for Gradient {
+    type Err = String;
+
+    /// Parses colors separated by commas, like `#ffffff,#ff8000`.
+    fn from_str(text: &str) -> Result<Gradient, String> {
+        let stops = text
+            .split(',')
+            .map(str::parse)
+            .collect::<Result<Vec<Color>, String>>()?;
+
+        Gradient::new(stops).ok_or_else(|| "a gradient needs at least one color".to_string())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HSVColor {
     pub h: f32,
@@ -133,9 +231,9 @@ impl From<HSVColor> for Color {
 
 impl HSVColor {
     /// generates a HSVColor with maximum saturation and value and a random hue.
-    pub fn random_hue() -> HSVColor {
+    pub fn random_hue(rng: &mut impl Rng) -> HSVColor {
         HSVColor {
-            h: rand::thread_rng().gen(),
+            h: rng.gen(),
             s: 1.0,
             v: 1.0,
         }
@@ -147,27 +245,34 @@ impl Character {
         Character {
             color: Color::black(),
             character: ' ',
+            background: None,
         }
     }
 
     pub fn render(&self) -> String {
+        let (r, g, b) = self.color.to_rgb8();
+        let background = match &self.background {
+            Some(background) => {
+                let (r, g, b) = background.to_rgb8();
+                format!("\x1b[48;2;{};{};{}m", r, g, b)
+            }
+            None => "\x1b[49m".to_string(),
+        };
         format!(
-            "\x1b[38;2;{};{};{}m{}",
-            (self.color.r * 256.0) as u8,
-            (self.color.g * 256.0) as u8,
-            (self.color.b * 256.0) as u8,
-            self.character
+            "{}\x1b[38;2;{};{};{}m{}",
+            background, r, g, b, self.character
         )
     }
 
     /// returns a Character with a random printable ASCII Character and a random color.
-    pub fn random() -> Character {
-        let character_code: u32 = rand::thread_rng().gen_range(32..128);
+    pub fn random(rng: &mut impl Rng) -> Character {
+        let character_code: u32 = rng.gen_range(32..128);
         let character = char::from_u32(character_code).unwrap();
 
         Character {
-            color: Color::from(HSVColor::random_hue()),
+            color: Color::from(HSVColor::random_hue(rng)),
             character,
+            background: None,
         }
     }
 }
@@ -181,6 +286,10 @@ impl<'a> Frame {
         }
     }
 
+    pub fn size(&self) -> Size {
+        Size::new(self.x, self.y)
+    }
+
     pub fn render(&self) {
         // a rougth estimate of the string size
         let mut output = String::with_capacity(self.x * self.y * 20);
@@ -240,7 +349,7 @@ impl<'a> Frame {
 /// values outside of the range are clipped.
 pub fn value_to_char(value: f32) -> char {
     let scale = "$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\\|()1{}[]?-_+~<>i!lI;:,\"^`'. ";
-    let value = value.min(1.0).max(0.0);
+    let value = value.clamp(0.0, 1.0);
 
     let mut index = (value * (scale.len() as f32)) as usize;
     if index == scale.len() {
@@ -249,3 +358,26 @@ pub fn value_to_char(value: f32) -> char {
 
     scale.chars().nth(index).unwrap()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gradient() {
+        let gradient: Gradient = "#000000, #ffffff,#ff0000".parse().unwrap();
+        assert_eq!(gradient.to_string(), "#000000,#ffffff,#ff0000");
+
+        assert_eq!(gradient.at(-1.0), Color::BLACK);
+        assert_eq!(gradient.at(0.25).r, 0.5);
+        assert_eq!(gradient.at(0.5), Color::WHITE);
+        assert_eq!(gradient.at(1.0).g, 0.0);
+
+        let single: Gradient = "ff8000".parse().unwrap();
+        assert_eq!(single.at(0.7), single.at(0.0));
+
+        assert!("#ff80".parse::<Gradient>().is_err());
+        assert!("#ff8000,".parse::<Gradient>().is_err());
+        assert!("#gg0000".parse::<Color>().is_err());
+    }
+}
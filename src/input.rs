@@ -0,0 +1,355 @@
+//! Keyboard and mouse input from the terminal.
+//!
+//! The terminal has to be in non canonical mode with mouse reporting enabled, see
+//! [`crate::terminal::Session`], otherwise input only arrives after Enter is pressed.
+use std::io::{self, IsTerminal, Read};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseKind {
+    Press(Button),
+    Release,
+    /// the mouse moved while the button is held down
+    Drag(Button),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// A mouse event at a cell of the frame, 0,0 is the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mouse {
+    pub kind: MouseKind,
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Key(Key),
+    Mouse(Mouse),
+}
+
+const ESC: u8 = 0x1B;
+
+/// Turns the bytes sent by the terminal into events.
+///
+/// Escape sequences and UTF-8 characters that are split between two reads are kept until the
+/// rest arrives.
+#[derive(Debug, Default)]
+pub struct Parser {
+    pending: Vec<u8>,
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        Parser::default()
+    }
+
+    pub fn parse(&mut self, bytes: &[u8]) -> Vec<Event> {
+        self.pending.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        let mut position = 0;
+
+        while position < self.pending.len() {
+            match self.parse_one(&self.pending[position..]) {
+                Parsed::Event(event, length) => {
+                    events.push(event);
+                    position += length;
+                }
+                Parsed::Ignored(length) => position += length,
+                Parsed::Incomplete => break,
+            }
+        }
+
+        self.pending.drain(..position);
+        events
+    }
+
+    fn parse_one(&self, bytes: &[u8]) -> Parsed {
+        let key = |key, length| Parsed::Event(Event::Key(key), length);
+
+        match bytes[0] {
+            ESC => match bytes.get(1) {
+                // a lone escape, sequences arrive in a single read
+                None => key(Key::Escape, 1),
+                Some(b'[') => parse_csi(bytes),
+                Some(b'O') => match bytes.get(2) {
+                    None => Parsed::Incomplete,
+                    Some(&final_byte) => match cursor_key(final_byte) {
+                        Some(cursor) => key(cursor, 3),
+                        None => Parsed::Ignored(3),
+                    },
+                },
+                // alt + key, only the escape is reported
+                Some(_) => key(Key::Escape, 1),
+            },
+            b'\r' | b'\n' => key(Key::Enter, 1),
+            b'\t' => key(Key::Tab, 1),
+            0x7F | 0x08 => key(Key::Backspace, 1),
+            byte if byte < 0x20 => Parsed::Ignored(1),
+            byte => {
+                let length = utf8_length(byte);
+                if bytes.len() < length {
+                    return Parsed::Incomplete;
+                }
+
+                match std::str::from_utf8(&bytes[..length]) {
+                    Ok(text) => key(Key::Char(text.chars().next().unwrap()), length),
+                    Err(_) => Parsed::Ignored(1),
+                }
+            }
+        }
+    }
+}
+
+enum Parsed {
+    /// an event and the number of bytes it used
+    Event(Event, usize),
+    /// the number of bytes that were skipped
+    Ignored(usize),
+    /// more bytes are needed
+    Incomplete,
+}
+
+/// Parses a control sequence like `ESC [ A` or `ESC [ 5 ~`.
+fn parse_csi(bytes: &[u8]) -> Parsed {
+    // parameters and intermediate bytes until the final byte
+    let Some(end) = bytes[2..]
+        .iter()
+        .position(|byte| (0x40..=0x7E).contains(byte))
+    else {
+        return Parsed::Incomplete;
+    };
+    let length = end + 3;
+    let parameters = &bytes[2..length - 1];
+    let final_byte = bytes[length - 1];
+
+    if let Some(mouse) = parameters.strip_prefix(b"<") {
+        return match parse_mouse(mouse, final_byte) {
+            Some(mouse) => Parsed::Event(Event::Mouse(mouse), length),
+            None => Parsed::Ignored(length),
+        };
+    }
+
+    let key = match final_byte {
+        b'~' => match parameters {
+            b"1" | b"7" => Some(Key::Home),
+            b"4" | b"8" => Some(Key::End),
+            b"3" => Some(Key::Delete),
+            b"5" => Some(Key::PageUp),
+            b"6" => Some(Key::PageDown),
+            _ => None,
+        },
+        _ => cursor_key(final_byte),
+    };
+
+    match key {
+        Some(key) => Parsed::Event(Event::Key(key), length),
+        None => Parsed::Ignored(length),
+    }
+}
+
+/// Parses the parameters of a SGR mouse report `ESC [ < button ; x ; y M`, the final byte is
+/// `m` when a button is released.
+fn parse_mouse(parameters: &[u8], final_byte: u8) -> Option<Mouse> {
+    let parameters = std::str::from_utf8(parameters).ok()?;
+    let mut numbers = parameters.split(';').map(|number| number.parse::<usize>());
+    let (Some(Ok(code)), Some(Ok(x)), Some(Ok(y)), None) = (
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+        numbers.next(),
+    ) else {
+        return None;
+    };
+
+    let button = match code & 0b11 {
+        0 => Button::Left,
+        1 => Button::Middle,
+        _ => Button::Right,
+    };
+
+    // the lower bits are the button, 32 is set for movements and 64 for the wheel,
+    // 4, 8 and 16 are the modifiers
+    let kind = if final_byte == b'm' {
+        MouseKind::Release
+    } else if code & 64 != 0 {
+        match code & 0b11 {
+            0 => MouseKind::ScrollUp,
+            1 => MouseKind::ScrollDown,
+            _ => return None,
+        }
+    } else if code & 32 != 0 {
+        // movement without a pressed button
+        if code & 0b11 == 3 {
+            return None;
+        }
+        MouseKind::Drag(button)
+    } else if final_byte == b'M' {
+        MouseKind::Press(button)
+    } else {
+        return None;
+    };
+
+    Some(Mouse {
+        kind,
+        x: x.checked_sub(1)?,
+        y: y.checked_sub(1)?,
+    })
+}
+
+fn cursor_key(final_byte: u8) -> Option<Key> {
+    match final_byte {
+        b'A' => Some(Key::Up),
+        b'B' => Some(Key::Down),
+        b'C' => Some(Key::Right),
+        b'D' => Some(Key::Left),
+        b'H' => Some(Key::Home),
+        b'F' => Some(Key::End),
+        _ => None,
+    }
+}
+
+/// The length of the UTF-8 sequence that starts with the given byte.
+fn utf8_length(byte: u8) -> usize {
+    match byte {
+        0xF0.. => 4,
+        0xE0.. => 3,
+        0xC0.. => 2,
+        _ => 1,
+    }
+}
+
+/// Reads the input in a background thread, so the main loop never blocks.
+pub struct Input {
+    events: Receiver<Event>,
+}
+
+impl Input {
+    /// Starts reading stdin, returns `None` if stdin is not a terminal.
+    pub fn spawn() -> Option<Input> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+
+        let (sender, events) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut parser = Parser::new();
+            let mut buffer = [0; 64];
+            let mut stdin = io::stdin();
+
+            loop {
+                let length = match stdin.read(&mut buffer) {
+                    Ok(0) | Err(_) => return,
+                    Ok(length) => length,
+                };
+
+                for event in parser.parse(&buffer[..length]) {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Some(Input { events })
+    }
+
+    /// Returns the next event if there is one.
+    pub fn poll(&self) -> Option<Event> {
+        self.events.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn keys(parser: &mut Parser, bytes: &[u8]) -> Vec<Key> {
+        parser
+            .parse(bytes)
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Key(key) => Some(key),
+                Event::Mouse(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut parser = Parser::new();
+
+        assert_eq!(
+            keys(&mut parser, b"n \x1B[A\x1B[5~\x1BOD\r\x1B"),
+            vec![
+                Key::Char('n'),
+                Key::Char(' '),
+                Key::Up,
+                Key::PageUp,
+                Key::Left,
+                Key::Enter,
+                Key::Escape
+            ]
+        );
+
+        // unknown sequences are skipped
+        assert_eq!(keys(&mut parser, b"\x1B[200~x"), vec![Key::Char('x')]);
+
+        // split sequences and characters are completed by the next read
+        assert_eq!(keys(&mut parser, b"\x1B[1"), vec![]);
+        assert_eq!(keys(&mut parser, b";5C\xC3"), vec![Key::Right]);
+        assert_eq!(keys(&mut parser, b"\xA4"), vec![Key::Char('ä')]);
+    }
+
+    #[test]
+    fn test_mouse() {
+        let mut parser = Parser::new();
+        let mouse = |kind, x, y| Event::Mouse(Mouse { kind, x, y });
+
+        assert_eq!(
+            parser.parse(b"\x1B[<0;10;5M\x1B[<32;11;5M\x1B[<0;11;5m\x1B[<65;1;1M\x1B[<2;3;4M"),
+            vec![
+                mouse(MouseKind::Press(Button::Left), 9, 4),
+                mouse(MouseKind::Drag(Button::Left), 10, 4),
+                mouse(MouseKind::Release, 10, 4),
+                mouse(MouseKind::ScrollDown, 0, 0),
+                mouse(MouseKind::Press(Button::Right), 2, 3),
+            ]
+        );
+
+        // movement without a button and broken reports are ignored
+        assert_eq!(
+            parser.parse(b"\x1B[<35;1;1M\x1B[<0;0;1Mx"),
+            vec![Event::Key(Key::Char('x'))]
+        );
+    }
+}
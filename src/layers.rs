@@ -0,0 +1,280 @@
+//! Stacking frames on top of each other.
+//!
+//! A [`Layer`] is a frame with an opacity for every cell, so parts of it can be transparent.
+//! The [`Compositor`] flattens a stack of layers from the bottom to the top into a single frame
+//! that can be presented.
+use crate::frame::{Character, Color, Frame, Size};
+
+/// How the colors of a layer are combined with the colors below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// the layer covers what is below
+    #[default]
+    Normal,
+    /// the colors are added, which brightens
+    Add,
+    /// the colors are multiplied, which darkens or tints
+    Multiply,
+    /// the inverted colors are multiplied, which brightens softer than adding
+    Screen,
+}
+
+impl BlendMode {
+    /// Combines the color below with the color of the layer, ignoring the opacity.
+    pub fn blend(&self, below: &Color, above: &Color) -> Color {
+        let channel = |below: f32, above: f32| match self {
+            BlendMode::Normal => above,
+            BlendMode::Add => (below + above).min(1.0),
+            BlendMode::Multiply => below * above,
+            BlendMode::Screen => 1.0 - (1.0 - below) * (1.0 - above),
+        };
+
+        Color {
+            r: channel(below.r, above.r),
+            g: channel(below.g, above.g),
+            b: channel(below.b, above.b),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub frame: Frame,
+    /// the opacity of every cell from 0.0 (transparent) to 1.0, in the order of `frame.data`
+    pub alpha: Vec<f32>,
+    /// the opacity of the whole layer, multiplied with the opacity of the cells
+    pub opacity: f32,
+    pub blend: BlendMode,
+}
+
+impl Layer {
+    /// Creates a completely transparent layer.
+    pub fn new(size: Size) -> Layer {
+        Layer {
+            frame: Frame::new(size.width, size.height),
+            alpha: vec![0.0; size.width * size.height],
+            opacity: 1.0,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Creates a layer that covers everything below it.
+    pub fn opaque(frame: Frame) -> Layer {
+        Layer {
+            alpha: vec![1.0; frame.data.len()],
+            frame,
+            opacity: 1.0,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    /// Creates a layer in which the blank cells of the frame are transparent.
+    pub fn keyed(frame: Frame) -> Layer {
+        Layer {
+            alpha: frame
+                .data
+                .iter()
+                .map(|cell| if cell.character == ' ' { 0.0 } else { 1.0 })
+                .collect(),
+            frame,
+            opacity: 1.0,
+            blend: BlendMode::Normal,
+        }
+    }
+
+    pub fn with_blend(mut self, blend: BlendMode) -> Layer {
+        self.blend = blend;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Layer {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn size(&self) -> Size {
+        self.frame.size()
+    }
+
+    /// Sets an opaque character, nothing is done if the position is outside of the layer.
+    pub fn set_at_clipping(&mut self, x: isize, y: isize, character: Character) {
+        let size = self.size();
+        if x >= 0 && (x as usize) < size.width && y >= 0 && (y as usize) < size.height {
+            let index = y as usize * size.width + x as usize;
+            self.frame.data[index] = character;
+            self.alpha[index] = 1.0;
+        }
+    }
+
+    /// Draws the layer onto the frame below it, cells outside of the frame are cut off.
+    ///
+    /// The color is blended with the blend mode and faded by the opacity. In the normal mode
+    /// the character of the layer replaces the one below if the cell is at least half opaque,
+    /// the other modes only change colors and keep the characters below, unless they are blank.
+    /// Backgrounds are blended like the colors, a covering cell without a background removes
+    /// the one below.
+    pub fn composite(&self, below: &mut Frame) {
+        let width = self.frame.x.min(below.x);
+        let height = self.frame.y.min(below.y);
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = y * self.frame.x + x;
+                let alpha = self.alpha[index] * self.opacity;
+                if alpha <= 0.0 {
+                    continue;
+                }
+
+                let above = &self.frame.data[index];
+                let cell = below.get_mut(x, y);
+                let blended = self.blend.blend(&cell.color, &above.color);
+
+                let covers = alpha >= 0.5
+                    && (self.blend == BlendMode::Normal
+                        || cell.character == ' ' && above.character != ' ');
+                if covers {
+                    cell.character = above.character;
+                }
+                cell.color = cell.color.interpolate(&blended, alpha);
+
+                // a missing background below blends like black
+                match &above.background {
+                    Some(background) => {
+                        let current = cell.background.take().unwrap_or(Color::BLACK);
+                        let blended = self.blend.blend(&current, background);
+                        cell.background = Some(current.interpolate(&blended, alpha));
+                    }
+                    None if covers && self.blend == BlendMode::Normal => cell.background = None,
+                    None => (),
+                }
+            }
+        }
+    }
+}
+
+/// A stack of layers, the first one is at the bottom.
+#[derive(Debug, Clone, Default)]
+pub struct Compositor {
+    pub layers: Vec<Layer>,
+}
+
+impl Compositor {
+    pub fn new() -> Compositor {
+        Compositor::default()
+    }
+
+    /// Puts the layer on top of the stack.
+    pub fn push(&mut self, layer: Layer) {
+        self.layers.push(layer);
+    }
+
+    /// Draws all layers from the bottom to the top onto a blank frame.
+    pub fn flatten(&self, size: Size) -> Frame {
+        let mut frame = Frame::new(size.width, size.height);
+        for layer in self.layers.iter() {
+            layer.composite(&mut frame);
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cell(character: char, color: Color) -> Character {
+        Character {
+            color,
+            character,
+            background: None,
+        }
+    }
+
+    fn gray(value: f32) -> Color {
+        Color {
+            r: value,
+            g: value,
+            b: value,
+        }
+    }
+
+    #[test]
+    fn test_blend() {
+        let below = gray(0.5);
+        let above = gray(0.5);
+
+        assert_eq!(BlendMode::Normal.blend(&below, &above), gray(0.5));
+        assert_eq!(BlendMode::Add.blend(&below, &above), gray(1.0));
+        assert_eq!(BlendMode::Multiply.blend(&below, &above), gray(0.25));
+        assert_eq!(BlendMode::Screen.blend(&below, &above), gray(0.75));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let size = Size::new(3, 1);
+        let mut bottom = Frame::new(3, 1);
+        for x in 0..3 {
+            bottom.set_at(x, 0, cell('a', gray(0.5)));
+        }
+
+        // only the middle cell of the top layer is drawn
+        let mut top = Layer::new(size);
+        top.set_at_clipping(1, 0, cell('b', Color::WHITE));
+        top.set_at_clipping(3, 0, cell('c', Color::WHITE));
+
+        let mut compositor = Compositor::new();
+        compositor.push(Layer::opaque(bottom.clone()));
+        compositor.push(top.clone());
+        let frame = compositor.flatten(size);
+
+        let text: String = frame.data.iter().map(|cell| cell.character).collect();
+        assert_eq!(text, "aba");
+        assert_eq!(frame.get(0, 0).color, gray(0.5));
+        assert_eq!(frame.get(1, 0).color, Color::WHITE);
+
+        // a faint layer only tints the colors
+        compositor.layers[1] = top.with_opacity(0.25);
+        let frame = compositor.flatten(size);
+        assert_eq!(frame.get(1, 0).character, 'a');
+        assert_eq!(frame.get(1, 0).color, gray(0.625));
+
+        // multiplying keeps the characters
+        let tint = Layer::opaque(Frame::new(3, 1)).with_blend(BlendMode::Multiply);
+        compositor.layers[1] = tint;
+        let frame = compositor.flatten(size);
+        assert_eq!(frame.get(2, 0).character, 'a');
+        assert_eq!(frame.get(2, 0).color, Color::BLACK);
+    }
+
+    #[test]
+    fn test_keyed() {
+        let mut frame = Frame::new(2, 1);
+        frame.set_at(1, 0, cell('x', Color::WHITE));
+
+        assert_eq!(Layer::keyed(frame).alpha, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_background() {
+        let mut bottom = Frame::new(2, 1);
+        bottom.get_mut(0, 0).background = Some(gray(0.5));
+        bottom.get_mut(1, 0).background = Some(gray(0.5));
+
+        let mut top = Layer::new(Size::new(2, 1));
+        top.set_at_clipping(0, 0, cell('x', Color::WHITE));
+        let mut above = cell('y', Color::WHITE);
+        above.background = Some(Color::WHITE);
+        top.set_at_clipping(1, 0, above);
+
+        let mut frame = bottom.clone();
+        top.composite(&mut frame);
+        assert_eq!(frame.get(0, 0).background, None);
+        assert_eq!(frame.get(1, 0).background, Some(Color::WHITE));
+
+        // a faint layer tints the background
+        let mut frame = bottom;
+        top.with_opacity(0.25).composite(&mut frame);
+        assert_eq!(frame.get(0, 0).background, Some(gray(0.5)));
+        assert_eq!(frame.get(1, 0).background, Some(gray(0.625)));
+    }
+}
@@ -1,4 +1,22 @@
 pub mod animations;
+pub mod automaton;
+pub mod canvas;
+pub mod clock;
+pub mod color_mode;
 pub mod filters;
 pub mod frame;
+pub mod input;
+pub mod layers;
+pub mod life;
+pub mod parameters;
+pub mod playlist;
+pub mod raster;
+pub mod renderer;
+pub mod runner;
+pub mod server;
+pub mod sink;
+pub mod telnet;
+pub mod terminal;
+pub mod transitions;
 pub mod utils;
+pub mod viewport;
@@ -0,0 +1,405 @@
+//! Life-like cellular automata: rules in B/S notation and patterns from pattern files.
+//!
+//! A [`Rule`] like `B3/S23` lists the numbers of living neighbors that give birth to a dead cell
+//! and those that let a living cell survive. A [`Pattern`] is read from the
+//! [RLE](https://conwaylife.com/wiki/Run_Length_Encoded) or the
+//! [plaintext](https://conwaylife.com/wiki/Plaintext) `.cells` format.
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A Life-like rule, bit `n` is set if `n` living neighbors give birth or let a cell survive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    pub birth: u16,
+    pub survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, B3/S23
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    /// the rules that can be given by name
+    const NAMED: [(&'static str, &'static str); 5] = [
+        ("conway", "B3/S23"),
+        ("life", "B3/S23"),
+        ("highlife", "B36/S23"),
+        ("day-and-night", "B3678/S34678"),
+        ("seeds", "B2/S"),
+    ];
+
+    /// Whether a cell is alive in the next generation.
+    pub fn next(&self, alive: bool, neighbors: usize) -> bool {
+        let rule = if alive { self.survival } else { self.birth };
+        rule & (1 << neighbors) != 0
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Rule {
+        Rule::CONWAY
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |bits: u16| -> String {
+            (0..=8)
+                .filter(|n| bits & (1 << n) != 0)
+                .map(|n| char::from_digit(n, 10).unwrap())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    /// Parses `B3/S23`, the older `23/3` (survival first) or one of the names like `highlife`.
+    fn from_str(text: &str) -> Result<Rule, String> {
+        let text = text.trim();
+        let error = || {
+            let names: Vec<&str> = Rule::NAMED.iter().map(|(name, _)| *name).collect();
+            format!(
+                "unknown rule '{}', use B/S notation like B3/S23 or one of {}",
+                text,
+                names.join(", ")
+            )
+        };
+
+        if let Some((_, rule)) = Rule::NAMED
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(text))
+        {
+            return rule.parse();
+        }
+
+        let digits = |text: &str| -> Option<u16> {
+            text.chars().try_fold(0, |bits, digit| match digit {
+                '0'..='8' => Some(bits | 1 << digit.to_digit(10).unwrap()),
+                _ => None,
+            })
+        };
+
+        let (first, second) = text.split_once('/').ok_or_else(error)?;
+        let (first, second) = (first.to_ascii_uppercase(), second.to_ascii_uppercase());
+        let (birth, survival) = match (first.strip_prefix('B'), second.strip_prefix('S')) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            // S23/B3
+            _ => match (first.strip_prefix('S'), second.strip_prefix('B')) {
+                (Some(survival), Some(birth)) => (birth, survival),
+                // 23/3
+                _ => (second.as_str(), first.as_str()),
+            },
+        };
+
+        Ok(Rule {
+            birth: digits(birth).ok_or_else(error)?,
+            survival: digits(survival).ok_or_else(error)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// the line of the file, starting at 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// The cells of a pattern, rows at the bottom without living cells are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub width: usize,
+    pub height: usize,
+    /// row by row
+    pub cells: Vec<bool>,
+    /// the rule given in the file, if any
+    pub rule: Option<Rule>,
+}
+
+impl Pattern {
+    /// the most cells of a pattern in either direction, larger ones are most likely broken files
+    pub const MAX_SIZE: usize = 4096;
+
+    /// Loads an RLE or plaintext pattern, the format is detected from the content.
+    pub fn load(path: &Path) -> Result<Pattern, PatternError> {
+        let text = fs::read_to_string(path).map_err(|error| PatternError {
+            line: None,
+            message: format!("could not read {}: {}", path.display(), error),
+        })?;
+
+        Pattern::parse(&text)
+    }
+
+    /// Parses a pattern, RLE files are recognized by their `x = ...` header line.
+    pub fn parse(text: &str) -> Result<Pattern, PatternError> {
+        let is_rle = text
+            .lines()
+            .map(str::trim_start)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .is_some_and(|line| line.starts_with('x') && line.contains('='));
+
+        if is_rle {
+            Pattern::from_rle(text)
+        } else {
+            Pattern::from_plaintext(text)
+        }
+    }
+
+    /// Parses the plaintext format, `O` is a living and `.` a dead cell, `!` starts a comment.
+    pub fn from_plaintext(text: &str) -> Result<Pattern, PatternError> {
+        let mut rows = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            if line.starts_with('!') {
+                continue;
+            }
+            if rows.len() >= Pattern::MAX_SIZE || line.len() > Pattern::MAX_SIZE {
+                return Err(PatternError {
+                    line: Some(index + 1),
+                    message: Pattern::too_large(),
+                });
+            }
+
+            let row = line
+                .trim_end()
+                .chars()
+                .map(|cell| match cell {
+                    'O' | 'o' | '*' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(PatternError {
+                        line: Some(index + 1),
+                        message: format!("unexpected '{}', cells are O or .", cell),
+                    }),
+                })
+                .collect::<Result<Vec<bool>, PatternError>>()?;
+            rows.push(row);
+        }
+
+        Ok(Pattern::from_rows(rows, None))
+    }
+
+    /// Parses the run length encoded format.
+    pub fn from_rle(text: &str) -> Result<Pattern, PatternError> {
+        let mut rule = None;
+        let mut header = false;
+        let mut rows = vec![Vec::new()];
+        let mut count: Option<usize> = None;
+
+        'lines: for (index, line) in text.lines().enumerate() {
+            let error = |message: String| PatternError {
+                line: Some(index + 1),
+                message,
+            };
+            let line = line.trim();
+
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            if !header {
+                if !line.starts_with('x') {
+                    return Err(error(
+                        "expected the header line x = ..., y = ...".to_string(),
+                    ));
+                }
+                header = true;
+                // the size is only a hint, the cells are counted
+                for field in line.split(',') {
+                    if let Some(("rule", value)) = field
+                        .split_once('=')
+                        .map(|(name, value)| (name.trim(), value.trim()))
+                    {
+                        rule = Some(value.parse().map_err(error)?);
+                    }
+                }
+                continue;
+            }
+
+            for tag in line.chars() {
+                if let Some(digit) = tag.to_digit(10) {
+                    count = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|count| count.checked_add(digit as usize))
+                        .filter(|count| *count <= Pattern::MAX_SIZE);
+                    if count.is_none() {
+                        return Err(error(Pattern::too_large()));
+                    }
+                    continue;
+                }
+                if tag.is_whitespace() {
+                    continue;
+                }
+
+                let run = count.take().unwrap_or(1);
+                let cells = match tag {
+                    'b' | '.' => false,
+                    '$' => {
+                        if rows.len() + run > Pattern::MAX_SIZE {
+                            return Err(error(Pattern::too_large()));
+                        }
+                        rows.extend((0..run).map(|_| Vec::new()));
+                        continue;
+                    }
+                    '!' => break 'lines,
+                    // other states of multi-state rules count as living
+                    tag if tag.is_ascii_alphabetic() => true,
+                    tag => return Err(error(format!("unexpected '{}'", tag))),
+                };
+
+                let row = rows.last_mut().unwrap();
+                if row.len() + run > Pattern::MAX_SIZE {
+                    return Err(error(Pattern::too_large()));
+                }
+                row.extend((0..run).map(|_| cells));
+            }
+        }
+
+        if !header {
+            return Err(PatternError {
+                line: None,
+                message: "the header line x = ..., y = ... is missing".to_string(),
+            });
+        }
+
+        Ok(Pattern::from_rows(rows, rule))
+    }
+
+    fn too_large() -> String {
+        format!(
+            "the pattern is larger than {0}x{0} cells",
+            Pattern::MAX_SIZE
+        )
+    }
+
+    /// The width is the one of the longest row up to its last living cell.
+    fn from_rows(mut rows: Vec<Vec<bool>>, rule: Option<Rule>) -> Pattern {
+        while rows.last().is_some_and(|row| !row.contains(&true)) {
+            rows.pop();
+        }
+
+        let width = rows
+            .iter()
+            .map(|row| row.iter().rposition(|&cell| cell).map_or(0, |x| x + 1))
+            .max()
+            .unwrap_or(0);
+        let height = rows.len();
+
+        let mut cells = vec![false; width * height];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &cell) in row.iter().enumerate().take(width) {
+                cells[y * width + x] = cell;
+            }
+        }
+
+        Pattern {
+            width,
+            height,
+            cells,
+            rule,
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        self.cells[y * self.width + x]
+    }
+
+    /// The number of living cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell).count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rule() {
+        assert_eq!("B3/S23".parse(), Ok(Rule::CONWAY));
+        assert_eq!("s23/b3".parse(), Ok(Rule::CONWAY));
+        assert_eq!("23/3".parse(), Ok(Rule::CONWAY));
+        assert_eq!("Life".parse(), Ok(Rule::CONWAY));
+
+        let highlife: Rule = "highlife".parse().unwrap();
+        assert_eq!(highlife.to_string(), "B36/S23");
+        assert!(highlife.next(false, 6));
+        assert!(!highlife.next(true, 6));
+
+        let seeds: Rule = "B2/S".parse().unwrap();
+        assert!(!seeds.next(true, 2));
+        assert!("B9/S23".parse::<Rule>().is_err());
+        assert!("conway's".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_rle() {
+        let glider = Pattern::parse("#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!").unwrap();
+        assert_eq!((glider.width, glider.height), (3, 3));
+        assert_eq!(glider.rule, Some(Rule::CONWAY));
+        assert!(glider.get(1, 0) && glider.get(2, 1) && glider.get(0, 2));
+        assert_eq!(glider.population(), 5);
+
+        // runs of lines, runs over several digits and line breaks
+        let pattern = Pattern::parse("x = 0, y = 0\no2$1\n2bo!").unwrap();
+        assert_eq!((pattern.width, pattern.height), (13, 3));
+        assert!(pattern.get(0, 0) && pattern.get(12, 2));
+
+        assert!(Pattern::from_rle("bo$2bo$3o!").is_err());
+    }
+
+    #[test]
+    fn test_too_large() {
+        // runs that overflow, are too long or add up to too many cells
+        for text in [
+            "x = 1, y = 1\n99999999999999999999o!",
+            "x = 1, y = 1\n4000000000o!",
+            "x = 1, y = 1\n4000o97b!",
+            "x = 1, y = 1\n4000$4000$o!",
+        ] {
+            let error = Pattern::parse(text).unwrap_err();
+            assert_eq!(error.line, Some(2), "{}", text);
+        }
+
+        let rows = ".O\n".repeat(Pattern::MAX_SIZE + 1);
+        assert!(Pattern::parse(&rows).is_err());
+    }
+
+    #[test]
+    fn test_plaintext() {
+        let glider = Pattern::parse("!Name: Glider\n!\n.O\n..O\nOOO\n\n").unwrap();
+        assert_eq!((glider.width, glider.height), (3, 3));
+        assert_eq!(glider.population(), 5);
+
+        let error = Pattern::parse("!Name: Broken\n.O\n.X\n").unwrap_err();
+        assert_eq!(error.line, Some(3));
+    }
+
+    #[test]
+    fn test_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("patterns");
+
+        for (name, population) in [("gosper-glider-gun.rle", 36), ("glider.cells", 5)] {
+            let pattern = Pattern::load(&directory.join(name)).unwrap();
+            assert_eq!(pattern.population(), population, "{}", name);
+        }
+    }
+}
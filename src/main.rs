@@ -1,5 +1,8 @@
-use crate::animations::{Animation, RandomWalkers, TextOverlay};
-use crate::frame::Frame;
+use asciimation::animations::{self, Animation, TextOverlay};
+use asciimation::frame::Size;
+use asciimation::renderer::Renderer;
+use asciimation::runner::Runner;
+use asciimation::terminal::Session;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -7,14 +10,21 @@ use std::time;
 
 use terminal_size::terminal_size;
 
-pub mod animations;
-pub mod frame;
-pub mod utils;
-
 fn main() {
     let mut last_step = time::Instant::now();
     let step_length = time::Duration::from_millis(16);
-    let mut animation = RandomWalkers::default();
+    // the animation can be given as the first argument
+    let name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "random-walkers".to_string());
+    let Some(entry) = animations::find(&name) else {
+        eprintln!("Unknown animation '{}'", name);
+        std::process::exit(1);
+    };
+    let mut runner = Runner::new((entry.create)());
+    let mut renderer = Renderer::new();
+    let start = time::Instant::now();
+    let session = Session::start().expect("Error setting up the terminal");
     let should_run = Arc::new(AtomicBool::new(true));
     ctrlc::set_handler({
         let should_run = should_run.clone();
@@ -30,16 +40,16 @@ fn main() {
         let (width, height) = size.unwrap();
 
         // sleep until we are ready
-        let sleep_time = step_length - last_step.elapsed();
+        let sleep_time = step_length.saturating_sub(last_step.elapsed());
 
         if sleep_time > time::Duration::ZERO {
             thread::sleep(sleep_time);
         }
 
         // build a frame
-        let mut frame = Frame::new(width.0 as usize, height.0 as usize);
-
-        animation.render(&mut frame);
+        let size = Size::new(width.0 as usize, height.0 as usize);
+        let (mut frame, time) = runner.render(size, start.elapsed());
+        let animation = runner.animation();
 
         // insert an overlay
         let mut overlay = TextOverlay {
@@ -52,11 +62,11 @@ fn main() {
             ),
         };
 
-        overlay.render(&mut frame);
+        overlay.render(&mut frame, &time);
 
-        frame.render();
+        renderer.render(&frame).unwrap();
         last_step = time::Instant::now();
     }
-    // Show cursor again
-    print!("\x1B[?25h");
+    runner.finish();
+    drop(session);
 }
@@ -0,0 +1,304 @@
+//! Typed parameters that animations expose so they can be changed without recompiling.
+//!
+//! An animation describes its parameters with `Animation::parameters` and receives already
+//! parsed and range checked values in `Animation::set_parameter`. Parsing the values from strings
+//! like `speed=4` or `center=-0.74,0.1` is done here, so the animations don't have to.
+use std::fmt;
+use std::str::FromStr;
+
+/// The type and the valid range of a parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    /// an integer between min and max, inclusive
+    Integer { min: i64, max: i64 },
+    /// a floating point number between min and max, inclusive
+    Float { min: f64, max: f64 },
+    /// a 2D point or complex number, written as `x,y`
+    Point,
+    /// `true` or `false`
+    Bool,
+    /// any text
+    Text,
+    /// one of the given names, the value is a `Value::Text`
+    Choice(&'static [&'static str]),
+}
+
+/// A parsed parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Point(f64, f64),
+    Bool(bool),
+    Text(String),
+}
+
+/// Describes a parameter of an animation.
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: Kind,
+    /// the current value
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    /// the animation has no parameter with this name
+    Unknown { name: String },
+    /// the value could not be parsed or is out of range
+    Invalid {
+        name: String,
+        value: String,
+        expected: String,
+    },
+    /// the parameter is not written in the expected form, e.g. `name=value`
+    Syntax {
+        text: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::Unknown { name } => write!(f, "unknown parameter '{}'", name),
+            ParameterError::Invalid {
+                name,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value '{}' for parameter '{}', expected {}",
+                value, name, expected
+            ),
+            ParameterError::Syntax { text, expected } => {
+                write!(f, "'{}' is not of the form {}", text, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+impl Kind {
+    /// Parses the text into a value of this kind and checks the range.
+    pub fn parse(&self, text: &str) -> Option<Value> {
+        let text = text.trim();
+
+        match self {
+            Kind::Integer { min, max } => {
+                let value: i64 = text.parse().ok()?;
+                (*min..=*max)
+                    .contains(&value)
+                    .then_some(Value::Integer(value))
+            }
+            Kind::Float { min, max } => {
+                let value: f64 = text.parse().ok()?;
+                (*min..=*max)
+                    .contains(&value)
+                    .then_some(Value::Float(value))
+            }
+            Kind::Point => {
+                let (x, y) = text.split_once(',')?;
+                let x: f64 = x.trim().parse().ok()?;
+                let y: f64 = y.trim().parse().ok()?;
+                (x.is_finite() && y.is_finite()).then_some(Value::Point(x, y))
+            }
+            Kind::Bool => match text {
+                "true" | "yes" | "on" | "1" => Some(Value::Bool(true)),
+                "false" | "no" | "off" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            Kind::Text => Some(Value::Text(text.to_string())),
+            Kind::Choice(names) => names
+                .iter()
+                .find(|name| name.eq_ignore_ascii_case(text))
+                .map(|name| Value::Text(name.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Integer { min, max } => write!(f, "an integer from {} to {}", min, max),
+            Kind::Float { min, max } => write!(f, "a number from {} to {}", min, max),
+            Kind::Point => write!(f, "a point x,y"),
+            Kind::Bool => write!(f, "true or false"),
+            Kind::Text => write!(f, "a text"),
+            Kind::Choice(names) => write!(f, "one of {}", names.join(", ")),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Point(x, y) => write!(f, "{},{}", x, y),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Value {
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Value::Float(value) => Some(*value),
+            Value::Integer(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_point(&self) -> Option<(f64, f64)> {
+        match self {
+            Value::Point(x, y) => Some((*x, *y)),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Finds the parameter in the description and parses the text for it.
+pub fn parse(parameters: &[Parameter], name: &str, text: &str) -> Result<Value, ParameterError> {
+    let parameter = parameters
+        .iter()
+        .find(|parameter| parameter.name == name)
+        .ok_or_else(|| ParameterError::Unknown {
+            name: name.to_string(),
+        })?;
+
+    parameter
+        .kind
+        .parse(text)
+        .ok_or_else(|| ParameterError::Invalid {
+            name: name.to_string(),
+            value: text.to_string(),
+            expected: parameter.kind.to_string(),
+        })
+}
+
+/// Splits `name=value` into its name and value.
+pub fn split_assignment(text: &str) -> Result<(&str, &str), ParameterError> {
+    match text.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim(), value.trim())),
+        _ => Err(ParameterError::Syntax {
+            text: text.to_string(),
+            expected: "name=value",
+        }),
+    }
+}
+
+/// A parameter for a specific animation, written as `animation.parameter=value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub animation: String,
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Assignment {
+    type Err = ParameterError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (key, value) = split_assignment(text).map_err(|_| ParameterError::Syntax {
+            text: text.to_string(),
+            expected: "animation.name=value",
+        })?;
+
+        match key.split_once('.') {
+            Some((animation, name)) if !animation.is_empty() && !name.is_empty() => {
+                Ok(Assignment {
+                    animation: animation.to_string(),
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+            }
+            _ => Err(ParameterError::Syntax {
+                text: text.to_string(),
+                expected: "animation.name=value",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let parameters = vec![
+            Parameter {
+                name: "speed",
+                description: "",
+                kind: Kind::Integer { min: 1, max: 10 },
+                value: Value::Integer(8),
+            },
+            Parameter {
+                name: "center",
+                description: "",
+                kind: Kind::Point,
+                value: Value::Point(0.0, 0.0),
+            },
+        ];
+
+        assert_eq!(parse(&parameters, "speed", "4"), Ok(Value::Integer(4)));
+        assert_eq!(
+            parse(&parameters, "center", "-0.74, 0.1"),
+            Ok(Value::Point(-0.74, 0.1))
+        );
+        assert!(matches!(
+            parse(&parameters, "speed", "11"),
+            Err(ParameterError::Invalid { .. })
+        ));
+        assert!(matches!(
+            parse(&parameters, "speed", "fast"),
+            Err(ParameterError::Invalid { .. })
+        ));
+        assert!(matches!(
+            parse(&parameters, "zoom", "1"),
+            Err(ParameterError::Unknown { .. })
+        ));
+
+        let choice = Kind::Choice(&["text", "braille"]);
+        assert_eq!(
+            choice.parse("Braille"),
+            Some(Value::Text("braille".to_string()))
+        );
+        assert_eq!(choice.parse("sixel"), None);
+
+        assert_eq!(split_assignment("speed = 4"), Ok(("speed", "4")));
+        assert!(split_assignment("speed").is_err());
+
+        let assignment: Assignment = "mandelbrot.center=-0.74,0.1".parse().unwrap();
+        assert_eq!(assignment.animation, "mandelbrot");
+        assert_eq!(assignment.name, "center");
+        assert_eq!(assignment.value, "-0.74,0.1");
+        assert!("speed=4".parse::<Assignment>().is_err());
+    }
+}
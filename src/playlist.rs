@@ -0,0 +1,506 @@
+//! Playlists describe the rotation of the shuffle binary.
+//!
+//! A playlist is a TOML or JSON file with some defaults and a list of entries:
+//!
+//! ```toml
+//! shuffle = false
+//! duration = 60.0
+//! fadeout = 2.0
+//! transition = "crossfade"
+//!
+//! [[entries]]
+//! animation = "gol"
+//! duration = 90.0
+//! transition = "random"
+//!
+//! [[entries]]
+//! animation = "mandelbrot"
+//! overlay = "Welcome!"
+//! weight = 2.0
+//! parameters = { center = "-0.74,0.1", zoom = 0.99 }
+//! ```
+use crate::animations::{self, Animation, Entry};
+use crate::transitions::Transition;
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// The playlist as it is written in the file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlaylistFile {
+    #[serde(default)]
+    shuffle: bool,
+    duration: Option<f32>,
+    fadeout: Option<f32>,
+    transition: Option<String>,
+    entries: Vec<EntryFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryFile {
+    animation: String,
+    duration: Option<f32>,
+    fadeout: Option<f32>,
+    transition: Option<String>,
+    overlay: Option<String>,
+    weight: Option<f32>,
+    #[serde(default)]
+    parameters: BTreeMap<String, ParameterText>,
+}
+
+/// Parameters can be written as strings or as plain TOML/JSON values.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ParameterText {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl fmt::Display for ParameterText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterText::Bool(value) => write!(f, "{}", value),
+            ParameterText::Integer(value) => write!(f, "{}", value),
+            ParameterText::Float(value) => write!(f, "{}", value),
+            ParameterText::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Playlist {
+    /// pick the next entry randomly by weight instead of playing them in order.
+    pub shuffle: bool,
+    pub entries: Vec<PlaylistEntry>,
+}
+
+#[derive(Debug)]
+pub struct PlaylistEntry {
+    pub animation: &'static Entry,
+    /// how long the animation is shown
+    pub duration: Duration,
+    /// how long the transition to the next entry takes
+    pub fadeout: Duration,
+    /// the transition to the next entry, picked randomly if not set
+    pub transition: Option<Transition>,
+    /// additional text that is shown in the overlay
+    pub overlay: Option<String>,
+    /// the relative probability of this entry when shuffling
+    pub weight: f32,
+    /// parameters of the animation as name and value
+    pub parameters: Vec<(String, String)>,
+}
+
+impl PlaylistEntry {
+    /// Creates the animation with the given seed and sets its parameters.
+    pub fn create(&self, seed: u64) -> Box<dyn Animation> {
+        let mut animation = self.animation.create_seeded(seed);
+
+        for (name, value) in self.parameters.iter() {
+            // the parameters were checked when the playlist was loaded
+            let _ = animation.set_parameter_from_str(name, value);
+        }
+
+        animation
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistError {
+    /// the index of the offending entry, `None` if the error is not about a single entry.
+    pub entry: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for PlaylistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.entry {
+            Some(index) => write!(f, "entry {}: {}", index + 1, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for PlaylistError {}
+
+impl Playlist {
+    /// Creates a playlist that shows the animations in order.
+    pub fn from_animations(
+        animations: &[&'static Entry],
+        duration: Duration,
+        fadeout: Duration,
+    ) -> Playlist {
+        Playlist {
+            shuffle: false,
+            entries: animations
+                .iter()
+                .map(|animation| PlaylistEntry {
+                    animation,
+                    duration,
+                    fadeout,
+                    transition: None,
+                    overlay: None,
+                    weight: 1.0,
+                    parameters: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Loads a playlist, files ending in `.json` are parsed as JSON, everything else as TOML.
+    ///
+    /// `duration` and `fadeout` are used for entries that do not set them and if the playlist
+    /// has no defaults.
+    pub fn load(
+        path: &Path,
+        duration: Duration,
+        fadeout: Duration,
+    ) -> Result<Playlist, PlaylistError> {
+        let text = fs::read_to_string(path).map_err(|error| PlaylistError {
+            entry: None,
+            message: format!("could not read {}: {}", path.display(), error),
+        })?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            Playlist::from_json(&text, duration, fadeout)
+        } else {
+            Playlist::from_toml(&text, duration, fadeout)
+        }
+    }
+
+    pub fn from_toml(
+        text: &str,
+        duration: Duration,
+        fadeout: Duration,
+    ) -> Result<Playlist, PlaylistError> {
+        let file: PlaylistFile = toml::from_str(text).map_err(|error| PlaylistError {
+            entry: None,
+            message: error.to_string(),
+        })?;
+
+        Playlist::validate(file, duration, fadeout)
+    }
+
+    pub fn from_json(
+        text: &str,
+        duration: Duration,
+        fadeout: Duration,
+    ) -> Result<Playlist, PlaylistError> {
+        let file: PlaylistFile = serde_json::from_str(text).map_err(|error| PlaylistError {
+            entry: None,
+            message: error.to_string(),
+        })?;
+
+        Playlist::validate(file, duration, fadeout)
+    }
+
+    fn validate(
+        file: PlaylistFile,
+        duration: Duration,
+        fadeout: Duration,
+    ) -> Result<Playlist, PlaylistError> {
+        let default_duration = seconds(file.duration, duration, None, "duration")?;
+        let default_fadeout = seconds(file.fadeout, fadeout, None, "fadeout")?;
+        let default_transition = transition(file.transition.as_deref(), None, None)?;
+
+        if file.entries.is_empty() {
+            return Err(PlaylistError {
+                entry: None,
+                message: "the playlist has no entries".to_string(),
+            });
+        }
+
+        let mut entries = Vec::with_capacity(file.entries.len());
+
+        for (index, entry) in file.entries.into_iter().enumerate() {
+            let error = |message: String| PlaylistError {
+                entry: Some(index),
+                message: format!("{} ({})", message, entry.animation),
+            };
+
+            let animation = animations::find(&entry.animation)
+                .ok_or_else(|| error("unknown animation".to_string()))?;
+
+            let duration = seconds(entry.duration, default_duration, Some(index), "duration")?;
+            let fadeout = seconds(entry.fadeout, default_fadeout, Some(index), "fadeout")?;
+            let transition =
+                transition(entry.transition.as_deref(), default_transition, Some(index))?;
+
+            if duration.is_zero() {
+                return Err(error("the duration must be greater than 0".to_string()));
+            }
+
+            // otherwise the next transition starts as soon as the animation is shown
+            if fadeout >= duration {
+                return Err(error(format!(
+                    "the fadeout of {}s is not shorter than the duration of {}s",
+                    fadeout.as_secs_f32(),
+                    duration.as_secs_f32()
+                )));
+            }
+
+            let weight = entry.weight.unwrap_or(1.0);
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(error(format!(
+                    "the weight must be a positive number, not {}",
+                    weight
+                )));
+            }
+
+            let parameters: Vec<(String, String)> = entry
+                .parameters
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_string()))
+                .collect();
+
+            let mut instance = (animation.create)();
+            for (name, value) in parameters.iter() {
+                instance
+                    .set_parameter_from_str(name, value)
+                    .map_err(|parameter_error| error(parameter_error.to_string()))?;
+            }
+
+            entries.push(PlaylistEntry {
+                animation,
+                duration,
+                fadeout,
+                transition,
+                overlay: entry.overlay,
+                weight,
+                parameters,
+            });
+        }
+
+        if file.shuffle && entries.iter().all(|entry| entry.weight == 0.0) {
+            return Err(PlaylistError {
+                entry: None,
+                message: "at least one entry needs a weight greater than 0".to_string(),
+            });
+        }
+
+        Ok(Playlist {
+            shuffle: file.shuffle,
+            entries,
+        })
+    }
+
+    /// Picks the index of the entry to show after the entry at `previous`.
+    ///
+    /// When shuffling, the same entry is not picked twice in a row if there are others.
+    pub fn next(&self, previous: Option<usize>, rng: &mut impl Rng) -> usize {
+        if !self.shuffle {
+            return match previous {
+                Some(previous) => (previous + 1) % self.entries.len(),
+                None => 0,
+            };
+        }
+
+        let weights = self.entries.iter().enumerate().map(|(index, entry)| {
+            if Some(index) == previous {
+                0.0
+            } else {
+                entry.weight
+            }
+        });
+
+        match WeightedIndex::new(weights) {
+            Ok(distribution) => distribution.sample(rng),
+            // only the previous entry has a weight
+            Err(_) => previous.unwrap_or(0),
+        }
+    }
+}
+
+/// Converts an optional number of seconds into a `Duration`, using the default if it is not set.
+fn seconds(
+    value: Option<f32>,
+    default: Duration,
+    entry: Option<usize>,
+    name: &str,
+) -> Result<Duration, PlaylistError> {
+    match value {
+        None => Ok(default),
+        Some(value) => Duration::try_from_secs_f32(value).map_err(|_| PlaylistError {
+            entry,
+            message: format!("invalid {} of {}s", name, value),
+        }),
+    }
+}
+
+/// Parses an optional transition, "random" picks a new one every time.
+fn transition(
+    value: Option<&str>,
+    default: Option<Transition>,
+    entry: Option<usize>,
+) -> Result<Option<Transition>, PlaylistError> {
+    match value {
+        None => Ok(default),
+        Some(value) if value.trim().eq_ignore_ascii_case("random") => Ok(None),
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|message| PlaylistError { entry, message }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DURATION: Duration = Duration::from_secs(60);
+    const FADEOUT: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn test_toml() {
+        let playlist = Playlist::from_toml(
+            r#"
+            duration = 30
+            transition = "wipe-vertical"
+
+            [[entries]]
+            animation = "gol"
+            duration = 90
+            fadeout = 5
+            transition = "random"
+
+            [[entries]]
+            animation = "Mandelbrot"
+            overlay = "Hello"
+            parameters = { center = "-0.74,0.1", zoom = 0.99 }
+            "#,
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap();
+
+        assert!(!playlist.shuffle);
+        assert_eq!(playlist.entries.len(), 2);
+        assert_eq!(playlist.entries[0].animation.id, "gol");
+        assert_eq!(playlist.entries[0].duration, Duration::from_secs(90));
+        assert_eq!(playlist.entries[0].fadeout, Duration::from_secs(5));
+        assert_eq!(playlist.entries[1].animation.id, "mandelbrot");
+        assert_eq!(playlist.entries[1].duration, Duration::from_secs(30));
+        assert_eq!(playlist.entries[1].fadeout, FADEOUT);
+        assert_eq!(playlist.entries[0].transition, None);
+        assert_eq!(
+            playlist.entries[1].transition,
+            Some(Transition::WipeVertical)
+        );
+        assert_eq!(playlist.entries[1].overlay.as_deref(), Some("Hello"));
+        assert_eq!(
+            playlist.entries[1].parameters,
+            vec![
+                ("center".to_string(), "-0.74,0.1".to_string()),
+                ("zoom".to_string(), "0.99".to_string())
+            ]
+        );
+
+        let mut rng = rand::thread_rng();
+        assert_eq!(playlist.next(None, &mut rng), 0);
+        assert_eq!(playlist.next(Some(0), &mut rng), 1);
+        assert_eq!(playlist.next(Some(1), &mut rng), 0);
+    }
+
+    #[test]
+    fn test_json() {
+        let playlist = Playlist::from_json(
+            r#"{"shuffle": true, "entries": [{"animation": "matrix", "weight": 3}, {"animation": "drops"}]}"#,
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap();
+
+        assert!(playlist.shuffle);
+        assert_eq!(playlist.entries[0].weight, 3.0);
+
+        // shuffling never repeats an entry if there is another one
+        let mut rng = rand::thread_rng();
+        assert_eq!(playlist.next(Some(0), &mut rng), 1);
+        assert_eq!(playlist.next(Some(1), &mut rng), 0);
+    }
+
+    #[test]
+    fn test_validation() {
+        let error = Playlist::from_toml(
+            r#"
+            [[entries]]
+            animation = "gol"
+
+            [[entries]]
+            animation = "does-not-exist"
+            "#,
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(error.entry, Some(1));
+        assert_eq!(
+            error.to_string(),
+            "entry 2: unknown animation (does-not-exist)"
+        );
+
+        let error = Playlist::from_toml(
+            "[[entries]]\nanimation = \"gol\"\nduration = 1\nfadeout = 2",
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(error.entry, Some(0));
+
+        let error = Playlist::from_toml(
+            "fadeout = 5\n[[entries]]\nanimation = \"gol\"\nduration = 5",
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "entry 1: the fadeout of 5s is not shorter than the duration of 5s (gol)"
+        );
+
+        let error = Playlist::from_toml(
+            "[[entries]]\nanimation = \"gol\"\nweight = -1",
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(error.entry, Some(0));
+
+        let error = Playlist::from_toml(
+            "[[entries]]\nanimation = \"gol\"\nparameters = { speed = 1000 }",
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(error.entry, Some(0));
+
+        let error = Playlist::from_toml(
+            "[[entries]]\nanimation = \"gol\"\ntransition = \"spin\"",
+            DURATION,
+            FADEOUT,
+        )
+        .unwrap_err();
+        assert_eq!(error.entry, Some(0));
+
+        assert!(Playlist::from_toml("entries = []", DURATION, FADEOUT).is_err());
+        assert!(Playlist::from_toml(
+            "[[entries]]\nanimation = \"gol\"\ncolour = 1",
+            DURATION,
+            FADEOUT
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,355 @@
+//! Rasterizes frames into pixel images using an embedded bitmap font.
+//!
+//! This is used to export animations as PNG sequences or animated GIFs without a terminal.
+use crate::frame::{Character, Color, Frame};
+use crate::sink::Sink;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod font;
+
+pub use font::{HEIGHT as CELL_HEIGHT, WIDTH as CELL_WIDTH};
+
+/// An RGB image with 8 bits per channel.
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    /// Creates a black image.
+    pub fn new(width: usize, height: usize) -> Image {
+        Image {
+            width,
+            height,
+            data: vec![0; width * height * 3],
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: (u8, u8, u8)) {
+        let index = (y * self.width + x) * 3;
+        self.data[index] = color.0;
+        self.data[index + 1] = color.1;
+        self.data[index + 2] = color.2;
+    }
+
+    pub fn fill_rect(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        color: (u8, u8, u8),
+    ) {
+        for y in y..(y + height).min(self.height) {
+            for x in x..(x + width).min(self.width) {
+                self.set(x, y, color);
+            }
+        }
+    }
+}
+
+/// Draws every character of the frame with the embedded font into an image.
+///
+/// Each cell is `CELL_WIDTH` x `CELL_HEIGHT` pixels large.
+pub fn rasterize(frame: &Frame) -> Image {
+    let mut image = Image::new(frame.x * CELL_WIDTH, frame.y * CELL_HEIGHT);
+
+    for y in 0..frame.y {
+        for x in 0..frame.x {
+            draw_character(&mut image, x * CELL_WIDTH, y * CELL_HEIGHT, frame.get(x, y));
+        }
+    }
+
+    image
+}
+
+fn draw_character(image: &mut Image, x: usize, y: usize, character: &Character) {
+    let background = match &character.background {
+        Some(background) => {
+            let background = background.to_rgb8();
+            image.fill_rect(x, y, CELL_WIDTH, CELL_HEIGHT, background);
+            background
+        }
+        None => Color::BLACK.to_rgb8(),
+    };
+
+    let color = character.color.to_rgb8();
+    if color == background {
+        return;
+    }
+
+    let code = character.character as u32;
+
+    if let Some(glyph) = font::glyph(character.character) {
+        for (row, bits) in glyph.iter().enumerate() {
+            for column in 0..CELL_WIDTH {
+                if bits & (1 << (CELL_WIDTH - 1 - column)) != 0 {
+                    image.set(x + column, y + row, color);
+                }
+            }
+        }
+    } else if (0x2580..=0x259f).contains(&code) {
+        draw_block_element(image, x, y, code, color);
+    } else if (0x2800..=0x28ff).contains(&code) {
+        draw_braille(image, x, y, code - 0x2800, color);
+    } else {
+        // unknown characters are drawn as a hollow box
+        image.fill_rect(x + 1, y + 2, CELL_WIDTH - 2, 1, color);
+        image.fill_rect(x + 1, y + CELL_HEIGHT - 3, CELL_WIDTH - 2, 1, color);
+        image.fill_rect(x + 1, y + 2, 1, CELL_HEIGHT - 4, color);
+        image.fill_rect(x + CELL_WIDTH - 2, y + 2, 1, CELL_HEIGHT - 4, color);
+    }
+}
+
+/// Draws the block elements from U+2580 to U+259F, as far as they are rectangles.
+fn draw_block_element(image: &mut Image, x: usize, y: usize, code: u32, color: (u8, u8, u8)) {
+    let half_width = CELL_WIDTH / 2;
+    let half_height = CELL_HEIGHT / 2;
+
+    // the quadrants that are filled: top left, top right, bottom left, bottom right
+    let quadrants = match code {
+        // upper half block
+        0x2580 => [true, true, false, false],
+        // lower half block
+        0x2584 => [false, false, true, true],
+        // full block
+        0x2588 => [true, true, true, true],
+        // left and right half blocks
+        0x258c => [true, false, true, false],
+        0x2590 => [false, true, false, true],
+        // shades are approximated with a full block
+        0x2591..=0x2593 => [true, true, true, true],
+        0x2596 => [false, false, true, false],
+        0x2597 => [false, false, false, true],
+        0x2598 => [true, false, false, false],
+        0x2599 => [true, false, true, true],
+        0x259a => [true, false, false, true],
+        0x259b => [true, true, true, false],
+        0x259c => [true, true, false, true],
+        0x259d => [false, true, false, false],
+        0x259e => [false, true, true, false],
+        0x259f => [false, true, true, true],
+        // the eighth blocks are rounded to the nearest half
+        0x2581..=0x2583 => [false, false, true, true],
+        0x2585..=0x2587 => [true, true, true, true],
+        0x2589..=0x258b => [true, true, true, true],
+        0x258d..=0x258f => [true, false, true, false],
+        _ => [false, false, false, false],
+    };
+
+    // with an odd cell size the right and bottom quadrants get the extra pixel, so blocks touch
+    let (right_width, bottom_height) = (CELL_WIDTH - half_width, CELL_HEIGHT - half_height);
+    let rects = [
+        (0, 0, half_width, half_height),
+        (half_width, 0, right_width, half_height),
+        (0, half_height, half_width, bottom_height),
+        (half_width, half_height, right_width, bottom_height),
+    ];
+
+    for (filled, (x_offset, y_offset, width, height)) in quadrants.iter().zip(rects) {
+        if *filled {
+            image.fill_rect(x + x_offset, y + y_offset, width, height, color);
+        }
+    }
+}
+
+/// Draws a braille pattern, `dots` is the offset from U+2800.
+fn draw_braille(image: &mut Image, x: usize, y: usize, dots: u32, color: (u8, u8, u8)) {
+    // the position of each of the 8 dots in a 2x4 grid
+    const DOTS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+
+    for (bit, (column, row)) in DOTS.iter().enumerate() {
+        if dots & (1 << bit) != 0 {
+            image.fill_rect(x + 1 + column * 3, y + 1 + row * 3, 2, 2, color);
+        }
+    }
+}
+
+/// Writes the image as a PNG file.
+pub fn write_png(out: impl Write, image: &Image) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, image.width as u32, image.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&image.data)
+        .map_err(io::Error::other)
+}
+
+/// Writes every presented frame as a numbered PNG file into a directory.
+pub struct PngSequenceSink {
+    directory: PathBuf,
+    index: usize,
+}
+
+impl PngSequenceSink {
+    /// creates the directory if it does not exist yet.
+    pub fn new(directory: impl Into<PathBuf>) -> io::Result<PngSequenceSink> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        Ok(PngSequenceSink {
+            directory,
+            index: 0,
+        })
+    }
+}
+
+impl Sink for PngSequenceSink {
+    fn present(&mut self, frame: &Frame, _time: Duration) -> io::Result<()> {
+        let path = self.directory.join(format!("frame_{:05}.png", self.index));
+        let file = BufWriter::new(File::create(path)?);
+
+        write_png(file, &rasterize(frame))?;
+        self.index += 1;
+
+        Ok(())
+    }
+}
+
+/// Writes the presented frames as an endlessly looping animated GIF.
+///
+/// The size of the GIF is taken from the first frame, later frames are cropped or padded to it.
+pub struct GifSink<W: Write> {
+    out: Option<W>,
+    encoder: Option<gif::Encoder<W>>,
+    /// the size of the GIF in pixels
+    size: (usize, usize),
+    /// the last frame and the time it was presented, it is written once its delay is known.
+    pending: Option<(Image, Duration)>,
+    /// the duration of all written frames in 1/100 s.
+    written_centis: u64,
+}
+
+impl<W: Write> GifSink<W> {
+    pub fn new(out: W) -> GifSink<W> {
+        GifSink {
+            out: Some(out),
+            encoder: None,
+            size: (0, 0),
+            pending: None,
+            written_centis: 0,
+        }
+    }
+
+    fn write_pending(&mut self, until: Option<Duration>) -> io::Result<()> {
+        let Some((image, time)) = self.pending.take() else {
+            return Ok(());
+        };
+
+        // GIF delays are in 1/100 s, rounding errors are carried over to the next frame
+        let end_centis = match until {
+            Some(until) => (until.as_secs_f64() * 100.0).round() as u64,
+            None => (time.as_secs_f64() * 100.0).round() as u64 + 2,
+        };
+        let delay = end_centis.saturating_sub(self.written_centis).max(1);
+        self.written_centis += delay;
+
+        if self.encoder.is_none() {
+            let out = self.out.take().expect("GIF output already consumed");
+            let mut encoder = gif::Encoder::new(out, image.width as u16, image.height as u16, &[])
+                .map_err(io::Error::other)?;
+            encoder
+                .set_repeat(gif::Repeat::Infinite)
+                .map_err(io::Error::other)?;
+            self.encoder = Some(encoder);
+            self.size = (image.width, image.height);
+        }
+        let encoder = self.encoder.as_mut().unwrap();
+
+        let image = fit(&image, self.size.0, self.size.1);
+        let mut frame =
+            gif::Frame::from_rgb_speed(image.width as u16, image.height as u16, &image.data, 10);
+        frame.delay = delay as u16;
+
+        encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+}
+
+impl<W: Write> Sink for GifSink<W> {
+    fn present(&mut self, frame: &Frame, time: Duration) -> io::Result<()> {
+        self.write_pending(Some(time))?;
+        self.pending = Some((rasterize(frame), time));
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.write_pending(None)?;
+
+        match self.encoder.as_mut() {
+            Some(encoder) => encoder.get_mut().flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// crops or pads the image to the given size.
+fn fit(image: &Image, width: usize, height: usize) -> Image {
+    if image.width == width && image.height == height {
+        return image.clone();
+    }
+
+    let mut fitted = Image::new(width, height);
+    for y in 0..height.min(image.height) {
+        let row = y * image.width * 3;
+        let length = width.min(image.width) * 3;
+        fitted.data[y * width * 3..y * width * 3 + length]
+            .copy_from_slice(&image.data[row..row + length]);
+    }
+
+    fitted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rasterize() {
+        let mut frame = Frame::new(2, 1);
+        frame.set_at(
+            1,
+            0,
+            Character {
+                character: '█',
+                color: Color::white(),
+                background: None,
+            },
+        );
+
+        let image = rasterize(&frame);
+
+        assert_eq!(image.width, 2 * CELL_WIDTH);
+        assert_eq!(image.height, CELL_HEIGHT);
+        // the empty cell stays black, every pixel of the full block is white
+        for y in 0..CELL_HEIGHT {
+            let row = &image.data[y * image.width * 3..(y + 1) * image.width * 3];
+            assert!(
+                row[..CELL_WIDTH * 3].iter().all(|value| *value == 0),
+                "{}",
+                y
+            );
+            assert!(
+                row[CELL_WIDTH * 3..].iter().all(|value| *value == 255),
+                "{}",
+                y
+            );
+        }
+    }
+}
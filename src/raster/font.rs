@@ -0,0 +1,789 @@
+//! A 6x13 pixel bitmap font covering ASCII and Latin-1.
+//!
+//! The glyphs are taken from the public domain "fixed" font of the X.Org project
+//! (misc-fixed 6x13). Every glyph is stored as 13 rows, the most significant of the lower 6 bits
+//! is the leftmost pixel.
+
+pub const WIDTH: usize = 6;
+pub const HEIGHT: usize = 13;
+
+/// glyphs for the characters from ' ' to '~'
+const ASCII: [[u8; HEIGHT]; 95] = [
+    // ' '
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '!'
+    [
+        0x00, 0x00, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x08, 0x00, 0x00,
+    ],
+    // '"'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '#'
+    [
+        0x00, 0x00, 0x00, 0x14, 0x14, 0x3e, 0x14, 0x3e, 0x14, 0x14, 0x00, 0x00, 0x00,
+    ],
+    // '$'
+    [
+        0x00, 0x00, 0x08, 0x1e, 0x28, 0x28, 0x1c, 0x0a, 0x0a, 0x3c, 0x08, 0x00, 0x00,
+    ],
+    // '%'
+    [
+        0x00, 0x00, 0x12, 0x2a, 0x14, 0x04, 0x08, 0x10, 0x14, 0x2a, 0x24, 0x00, 0x00,
+    ],
+    // '&'
+    [
+        0x00, 0x00, 0x00, 0x10, 0x28, 0x28, 0x10, 0x28, 0x26, 0x24, 0x1a, 0x00, 0x00,
+    ],
+    // '\''
+    [
+        0x00, 0x00, 0x08, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '('
+    [
+        0x00, 0x04, 0x08, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10, 0x08, 0x08, 0x04, 0x00,
+    ],
+    // ')'
+    [
+        0x00, 0x10, 0x08, 0x08, 0x04, 0x04, 0x04, 0x04, 0x04, 0x08, 0x08, 0x10, 0x00,
+    ],
+    // '*'
+    [
+        0x00, 0x00, 0x08, 0x2a, 0x1c, 0x2a, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '+'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x08, 0x3e, 0x08, 0x08, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // ','
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x08, 0x10, 0x00,
+    ],
+    // '-'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '.'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x1c, 0x08, 0x00,
+    ],
+    // '/'
+    [
+        0x00, 0x00, 0x02, 0x02, 0x04, 0x04, 0x08, 0x10, 0x10, 0x20, 0x20, 0x00, 0x00,
+    ],
+    // '0'
+    [
+        0x00, 0x00, 0x08, 0x14, 0x22, 0x22, 0x22, 0x22, 0x22, 0x14, 0x08, 0x00, 0x00,
+    ],
+    // '1'
+    [
+        0x00, 0x00, 0x08, 0x18, 0x28, 0x08, 0x08, 0x08, 0x08, 0x08, 0x3e, 0x00, 0x00,
+    ],
+    // '2'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x02, 0x04, 0x08, 0x10, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // '3'
+    [
+        0x00, 0x00, 0x3e, 0x02, 0x04, 0x08, 0x1c, 0x02, 0x02, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '4'
+    [
+        0x00, 0x00, 0x04, 0x04, 0x0c, 0x14, 0x14, 0x24, 0x3e, 0x04, 0x04, 0x00, 0x00,
+    ],
+    // '5'
+    [
+        0x00, 0x00, 0x3e, 0x20, 0x20, 0x2c, 0x32, 0x02, 0x02, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '6'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x3c, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '7'
+    [
+        0x00, 0x00, 0x3e, 0x02, 0x04, 0x04, 0x08, 0x08, 0x10, 0x10, 0x10, 0x00, 0x00,
+    ],
+    // '8'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x1c, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '9'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x1e, 0x02, 0x02, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // ':'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x1c, 0x08, 0x00, 0x00, 0x08, 0x1c, 0x08, 0x00,
+    ],
+    // ';'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x08, 0x1c, 0x08, 0x00, 0x00, 0x0c, 0x08, 0x10, 0x00,
+    ],
+    // '<'
+    [
+        0x00, 0x00, 0x02, 0x04, 0x08, 0x10, 0x20, 0x10, 0x08, 0x04, 0x02, 0x00, 0x00,
+    ],
+    // '='
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x00, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '>'
+    [
+        0x00, 0x00, 0x20, 0x10, 0x08, 0x04, 0x02, 0x04, 0x08, 0x10, 0x20, 0x00, 0x00,
+    ],
+    // '?'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x02, 0x04, 0x08, 0x08, 0x00, 0x08, 0x00, 0x00,
+    ],
+    // '@'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x26, 0x2a, 0x2a, 0x2c, 0x20, 0x1e, 0x00, 0x00,
+    ],
+    // 'A'
+    [
+        0x00, 0x00, 0x08, 0x14, 0x22, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'B'
+    [
+        0x00, 0x00, 0x3c, 0x12, 0x12, 0x12, 0x1c, 0x12, 0x12, 0x12, 0x3c, 0x00, 0x00,
+    ],
+    // 'C'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x20, 0x20, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'D'
+    [
+        0x00, 0x00, 0x3c, 0x12, 0x12, 0x12, 0x12, 0x12, 0x12, 0x12, 0x3c, 0x00, 0x00,
+    ],
+    // 'E'
+    [
+        0x00, 0x00, 0x3e, 0x20, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'F'
+    [
+        0x00, 0x00, 0x3e, 0x20, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00,
+    ],
+    // 'G'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x20, 0x26, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'H'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'I'
+    [
+        0x00, 0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'J'
+    [
+        0x00, 0x00, 0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x24, 0x18, 0x00, 0x00,
+    ],
+    // 'K'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x24, 0x28, 0x30, 0x28, 0x24, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'L'
+    [
+        0x00, 0x00, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'M'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x36, 0x2a, 0x2a, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'N'
+    [
+        0x00, 0x00, 0x22, 0x32, 0x32, 0x2a, 0x2a, 0x26, 0x26, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'O'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'P'
+    [
+        0x00, 0x00, 0x3c, 0x22, 0x22, 0x22, 0x3c, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00,
+    ],
+    // 'Q'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x2a, 0x1c, 0x02, 0x00,
+    ],
+    // 'R'
+    [
+        0x00, 0x00, 0x3c, 0x22, 0x22, 0x22, 0x3c, 0x28, 0x24, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'S'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x1c, 0x02, 0x02, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'T'
+    [
+        0x00, 0x00, 0x3e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // 'U'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'V'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x14, 0x14, 0x14, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // 'W'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x2a, 0x2a, 0x2a, 0x2a, 0x14, 0x00, 0x00,
+    ],
+    // 'X'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x14, 0x14, 0x08, 0x14, 0x14, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Y'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x14, 0x14, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // 'Z'
+    [
+        0x00, 0x00, 0x3e, 0x02, 0x04, 0x04, 0x08, 0x10, 0x10, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // '['
+    [
+        0x00, 0x1c, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1c, 0x00,
+    ],
+    // '\\'
+    [
+        0x00, 0x00, 0x20, 0x20, 0x10, 0x10, 0x08, 0x04, 0x04, 0x02, 0x02, 0x00, 0x00,
+    ],
+    // ']'
+    [
+        0x00, 0x1c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x1c, 0x00,
+    ],
+    // '^'
+    [
+        0x00, 0x00, 0x08, 0x14, 0x22, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '_'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x00,
+    ],
+    // '`'
+    [
+        0x00, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // 'a'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'b'
+    [
+        0x00, 0x00, 0x20, 0x20, 0x20, 0x3c, 0x22, 0x22, 0x22, 0x22, 0x3c, 0x00, 0x00,
+    ],
+    // 'c'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'd'
+    [
+        0x00, 0x00, 0x02, 0x02, 0x02, 0x1e, 0x22, 0x22, 0x22, 0x22, 0x1e, 0x00, 0x00,
+    ],
+    // 'e'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'f'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x10, 0x10, 0x3c, 0x10, 0x10, 0x10, 0x10, 0x00, 0x00,
+    ],
+    // 'g'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x1e, 0x02, 0x22, 0x1c,
+    ],
+    // 'h'
+    [
+        0x00, 0x00, 0x20, 0x20, 0x20, 0x2c, 0x32, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'i'
+    [
+        0x00, 0x00, 0x00, 0x08, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'j'
+    [
+        0x00, 0x00, 0x00, 0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x24, 0x24, 0x18,
+    ],
+    // 'k'
+    [
+        0x00, 0x00, 0x20, 0x20, 0x20, 0x24, 0x28, 0x30, 0x28, 0x24, 0x22, 0x00, 0x00,
+    ],
+    // 'l'
+    [
+        0x00, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'm'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x34, 0x2a, 0x2a, 0x2a, 0x2a, 0x22, 0x00, 0x00,
+    ],
+    // 'n'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x32, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'o'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'p'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x3c, 0x22, 0x22, 0x22, 0x3c, 0x20, 0x20, 0x20,
+    ],
+    // 'q'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1e, 0x22, 0x22, 0x22, 0x1e, 0x02, 0x02, 0x02,
+    ],
+    // 'r'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x32, 0x20, 0x20, 0x20, 0x20, 0x00, 0x00,
+    ],
+    // 's'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x18, 0x04, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 't'
+    [
+        0x00, 0x00, 0x00, 0x10, 0x10, 0x3c, 0x10, 0x10, 0x10, 0x12, 0x0c, 0x00, 0x00,
+    ],
+    // 'u'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'v'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x14, 0x14, 0x08, 0x00, 0x00,
+    ],
+    // 'w'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x2a, 0x2a, 0x2a, 0x14, 0x00, 0x00,
+    ],
+    // 'x'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x14, 0x08, 0x08, 0x14, 0x22, 0x00, 0x00,
+    ],
+    // 'y'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x02, 0x22, 0x1c,
+    ],
+    // 'z'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x04, 0x08, 0x10, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // '{'
+    [
+        0x00, 0x06, 0x08, 0x08, 0x08, 0x08, 0x30, 0x08, 0x08, 0x08, 0x08, 0x06, 0x00,
+    ],
+    // '|'
+    [
+        0x00, 0x00, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // '}'
+    [
+        0x00, 0x30, 0x08, 0x08, 0x08, 0x08, 0x06, 0x08, 0x08, 0x08, 0x08, 0x30, 0x00,
+    ],
+    // '~'
+    [
+        0x00, 0x00, 0x12, 0x2a, 0x24, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+];
+
+/// glyphs for the characters from '\xa0' to 'ÿ'
+const LATIN_1: [[u8; HEIGHT]; 96] = [
+    // '\u{a0}'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '¡'
+    [
+        0x00, 0x00, 0x08, 0x00, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // '¢'
+    [
+        0x00, 0x00, 0x08, 0x1c, 0x2a, 0x28, 0x28, 0x2a, 0x1c, 0x08, 0x00, 0x00, 0x00,
+    ],
+    // '£'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x10, 0x10, 0x38, 0x10, 0x10, 0x12, 0x2c, 0x00, 0x00,
+    ],
+    // '¤'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x22, 0x1c, 0x14, 0x14, 0x1c, 0x22, 0x00, 0x00, 0x00,
+    ],
+    // '¥'
+    [
+        0x00, 0x00, 0x22, 0x22, 0x14, 0x14, 0x3e, 0x08, 0x3e, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // '¦'
+    [
+        0x00, 0x00, 0x08, 0x08, 0x08, 0x08, 0x00, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // '§'
+    [
+        0x00, 0x0c, 0x12, 0x10, 0x0c, 0x12, 0x12, 0x0c, 0x02, 0x12, 0x0c, 0x00, 0x00,
+    ],
+    // '¨'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '©'
+    [
+        0x00, 0x1c, 0x22, 0x2a, 0x36, 0x32, 0x36, 0x2a, 0x22, 0x1c, 0x00, 0x00, 0x00,
+    ],
+    // 'ª'
+    [
+        0x00, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x1e, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '«'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x0a, 0x14, 0x28, 0x28, 0x14, 0x0a, 0x00, 0x00, 0x00,
+    ],
+    // '¬'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x3e, 0x02, 0x02, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '\u{ad}'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '®'
+    [
+        0x00, 0x1c, 0x22, 0x3a, 0x36, 0x36, 0x3a, 0x36, 0x22, 0x1c, 0x00, 0x00, 0x00,
+    ],
+    // '¯'
+    [
+        0x00, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '°'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x12, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '±'
+    [
+        0x00, 0x00, 0x00, 0x08, 0x08, 0x3e, 0x08, 0x08, 0x00, 0x3e, 0x00, 0x00, 0x00,
+    ],
+    // '²'
+    [
+        0x00, 0x10, 0x28, 0x08, 0x10, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '³'
+    [
+        0x00, 0x10, 0x28, 0x10, 0x08, 0x30, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '´'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // 'µ'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x3a, 0x20, 0x20,
+    ],
+    // '¶'
+    [
+        0x00, 0x00, 0x1e, 0x3a, 0x3a, 0x3a, 0x3a, 0x1a, 0x0a, 0x0a, 0x0a, 0x00, 0x00,
+    ],
+    // '·'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '¸'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x08,
+    ],
+    // '¹'
+    [
+        0x00, 0x10, 0x30, 0x10, 0x10, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // 'º'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x3e, 0x00, 0x00, 0x00, 0x00,
+    ],
+    // '»'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x28, 0x14, 0x0a, 0x0a, 0x14, 0x28, 0x00, 0x00, 0x00,
+    ],
+    // '¼'
+    [
+        0x00, 0x10, 0x30, 0x10, 0x10, 0x38, 0x02, 0x06, 0x0a, 0x0e, 0x02, 0x00, 0x00,
+    ],
+    // '½'
+    [
+        0x00, 0x10, 0x30, 0x10, 0x10, 0x38, 0x04, 0x0a, 0x02, 0x04, 0x0e, 0x00, 0x00,
+    ],
+    // '¾'
+    [
+        0x00, 0x10, 0x28, 0x10, 0x08, 0x28, 0x12, 0x06, 0x0a, 0x0e, 0x02, 0x00, 0x00,
+    ],
+    // '¿'
+    [
+        0x00, 0x00, 0x08, 0x00, 0x08, 0x08, 0x10, 0x20, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'À'
+    [
+        0x00, 0x10, 0x08, 0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Á'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Â'
+    [
+        0x00, 0x0c, 0x12, 0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Ã'
+    [
+        0x00, 0x0a, 0x14, 0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Ä'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Å'
+    [
+        0x00, 0x08, 0x14, 0x08, 0x08, 0x14, 0x22, 0x22, 0x3e, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Æ'
+    [
+        0x00, 0x00, 0x16, 0x28, 0x28, 0x28, 0x2c, 0x38, 0x28, 0x28, 0x2e, 0x00, 0x00,
+    ],
+    // 'Ç'
+    [
+        0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x20, 0x20, 0x20, 0x22, 0x1c, 0x08, 0x10,
+    ],
+    // 'È'
+    [
+        0x00, 0x10, 0x08, 0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'É'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'Ê'
+    [
+        0x00, 0x0c, 0x12, 0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'Ë'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x3e, 0x20, 0x20, 0x3c, 0x20, 0x20, 0x3e, 0x00, 0x00,
+    ],
+    // 'Ì'
+    [
+        0x00, 0x10, 0x08, 0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'Í'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'Î'
+    [
+        0x00, 0x0c, 0x12, 0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ï'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x1c, 0x08, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ð'
+    [
+        0x00, 0x00, 0x3c, 0x12, 0x12, 0x12, 0x3a, 0x12, 0x12, 0x12, 0x3c, 0x00, 0x00,
+    ],
+    // 'Ñ'
+    [
+        0x00, 0x0a, 0x14, 0x00, 0x22, 0x22, 0x32, 0x2a, 0x26, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'Ò'
+    [
+        0x00, 0x10, 0x08, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ó'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ô'
+    [
+        0x00, 0x0c, 0x12, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Õ'
+    [
+        0x00, 0x0a, 0x14, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ö'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '×'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x14, 0x08, 0x14, 0x22, 0x00, 0x00, 0x00,
+    ],
+    // 'Ø'
+    [
+        0x00, 0x02, 0x1c, 0x26, 0x26, 0x2a, 0x2a, 0x2a, 0x32, 0x32, 0x1c, 0x20, 0x00,
+    ],
+    // 'Ù'
+    [
+        0x00, 0x10, 0x08, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ú'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Û'
+    [
+        0x00, 0x0c, 0x12, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ü'
+    [
+        0x00, 0x14, 0x14, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'Ý'
+    [
+        0x00, 0x04, 0x08, 0x00, 0x22, 0x22, 0x14, 0x08, 0x08, 0x08, 0x08, 0x00, 0x00,
+    ],
+    // 'Þ'
+    [
+        0x00, 0x00, 0x20, 0x3c, 0x22, 0x22, 0x22, 0x3c, 0x20, 0x20, 0x20, 0x00, 0x00,
+    ],
+    // 'ß'
+    [
+        0x00, 0x00, 0x18, 0x24, 0x24, 0x28, 0x28, 0x24, 0x22, 0x22, 0x2c, 0x00, 0x00,
+    ],
+    // 'à'
+    [
+        0x00, 0x00, 0x10, 0x08, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'á'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'â'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'ã'
+    [
+        0x00, 0x00, 0x0a, 0x14, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'ä'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'å'
+    [
+        0x00, 0x0c, 0x12, 0x0c, 0x00, 0x1c, 0x02, 0x1e, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'æ'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x0a, 0x1c, 0x28, 0x2a, 0x14, 0x00, 0x00,
+    ],
+    // 'ç'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x1c, 0x22, 0x20, 0x20, 0x22, 0x1c, 0x08, 0x10,
+    ],
+    // 'è'
+    [
+        0x00, 0x00, 0x10, 0x08, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'é'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ê'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ë'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x1c, 0x22, 0x3e, 0x20, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ì'
+    [
+        0x00, 0x00, 0x10, 0x08, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'í'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'î'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'ï'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x18, 0x08, 0x08, 0x08, 0x08, 0x1c, 0x00, 0x00,
+    ],
+    // 'ð'
+    [
+        0x00, 0x14, 0x08, 0x18, 0x04, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ñ'
+    [
+        0x00, 0x00, 0x0a, 0x14, 0x00, 0x2c, 0x32, 0x22, 0x22, 0x22, 0x22, 0x00, 0x00,
+    ],
+    // 'ò'
+    [
+        0x00, 0x00, 0x10, 0x08, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ó'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ô'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'õ'
+    [
+        0x00, 0x00, 0x0a, 0x14, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // 'ö'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x1c, 0x22, 0x22, 0x22, 0x22, 0x1c, 0x00, 0x00,
+    ],
+    // '÷'
+    [
+        0x00, 0x00, 0x00, 0x08, 0x08, 0x00, 0x3e, 0x00, 0x08, 0x08, 0x00, 0x00, 0x00,
+    ],
+    // 'ø'
+    [
+        0x00, 0x00, 0x00, 0x00, 0x02, 0x1c, 0x26, 0x2a, 0x2a, 0x32, 0x1c, 0x20, 0x00,
+    ],
+    // 'ù'
+    [
+        0x00, 0x00, 0x10, 0x08, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'ú'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'û'
+    [
+        0x00, 0x00, 0x0c, 0x12, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'ü'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x22, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x00, 0x00,
+    ],
+    // 'ý'
+    [
+        0x00, 0x00, 0x04, 0x08, 0x00, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x02, 0x22, 0x1c,
+    ],
+    // 'þ'
+    [
+        0x00, 0x00, 0x00, 0x20, 0x20, 0x2c, 0x32, 0x22, 0x22, 0x32, 0x2c, 0x20, 0x20,
+    ],
+    // 'ÿ'
+    [
+        0x00, 0x00, 0x14, 0x14, 0x00, 0x22, 0x22, 0x22, 0x26, 0x1a, 0x02, 0x22, 0x1c,
+    ],
+];
+
+/// returns the glyph for the character, `None` if the font does not contain it.
+pub fn glyph(character: char) -> Option<&'static [u8; HEIGHT]> {
+    match character {
+        ' '..='~' => Some(&ASCII[character as usize - ' ' as usize]),
+        '\u{a0}'..='ÿ' => Some(&LATIN_1[character as usize - 0xa0]),
+        _ => None,
+    }
+}
@@ -0,0 +1,251 @@
+//! Differential terminal output.
+//!
+//! Repainting every cell with its own color escape 60 times per second saturates slow
+//! terminals and SSH links. The `Renderer` remembers what it emitted last and only writes the
+//! cells that changed since then.
+use crate::color_mode::{ColorMode, Pen};
+use crate::frame::{Character, Frame};
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+pub struct Renderer {
+    color_mode: ColorMode,
+    /// the frame that was emitted last, `None` forces a full repaint.
+    previous: Option<Frame>,
+    /// the pen that is currently set in the terminal, if known.
+    pen: Option<Pen>,
+    /// the background pen that is currently set in the terminal, if known.
+    background: Option<Option<Pen>>,
+    /// the cursor position after the last write, if known.
+    cursor: Option<(usize, usize)>,
+    output: String,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer::new()
+    }
+}
+
+impl Renderer {
+    /// Creates a renderer for the color mode detected from the environment.
+    pub fn new() -> Renderer {
+        Renderer::with_color_mode(ColorMode::detect())
+    }
+
+    pub fn with_color_mode(color_mode: ColorMode) -> Renderer {
+        Renderer {
+            color_mode,
+            previous: None,
+            pen: None,
+            background: None,
+            cursor: None,
+            output: String::new(),
+        }
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Switches to another color mode and repaints the next frame completely.
+    pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+        self.color_mode = color_mode;
+        self.invalidate();
+    }
+
+    /// Forgets the terminal state, the next frame is repainted completely.
+    ///
+    /// Call this whenever something else wrote to the terminal.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+        self.pen = None;
+        self.background = None;
+        self.cursor = None;
+    }
+
+    /// Returns the number of bytes the last rendered frame needed.
+    pub fn bytes_per_frame(&self) -> usize {
+        self.output.len()
+    }
+
+    /// Builds the escape sequences that turn the previously emitted frame into the given one.
+    ///
+    /// The returned string is empty if nothing changed.
+    pub fn diff(&mut self, frame: &Frame) -> &str {
+        self.output.clear();
+
+        let full_repaint = match &self.previous {
+            Some(previous) => previous.x != frame.x || previous.y != frame.y,
+            None => true,
+        };
+
+        if full_repaint {
+            // hide the cursor and clear the screen, which fills it with the current background
+            self.output += "\x1B[?25l";
+            self.set_background(None);
+            self.output += "\x1B[2J";
+            self.cursor = None;
+        }
+
+        for y in 0..frame.y {
+            for x in 0..frame.x {
+                let character = frame.get(x, y);
+
+                if !full_repaint {
+                    if let Some(previous) = &self.previous {
+                        if self.same_cell(previous.get(x, y), character) {
+                            continue;
+                        }
+                    }
+                }
+
+                self.move_to(x, y);
+                let pen = self.color_mode.pen(&character.color);
+                self.set_pen(pen);
+                self.set_background(
+                    self.color_mode
+                        .background_pen(character.background.as_ref()),
+                );
+                if pen.visible() {
+                    self.output.push(character.character);
+                } else {
+                    self.output.push(' ');
+                }
+
+                // the cursor stays in the last column until the next character is written
+                self.cursor = if x + 1 < frame.x {
+                    Some((x + 1, y))
+                } else {
+                    None
+                };
+            }
+        }
+
+        match &mut self.previous {
+            Some(previous) if !full_repaint => previous.data.clone_from(&frame.data),
+            _ => self.previous = Some(frame.clone()),
+        }
+
+        &self.output
+    }
+
+    /// Renders the frame to stdout.
+    pub fn render(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        self.render_to(&mut stdout, frame)
+    }
+
+    /// Renders the frame to the given writer.
+    pub fn render_to(&mut self, out: &mut impl Write, frame: &Frame) -> io::Result<()> {
+        let output = self.diff(frame);
+        out.write_all(output.as_bytes())?;
+        out.flush()
+    }
+
+    fn move_to(&mut self, x: usize, y: usize) {
+        match self.cursor {
+            Some(cursor) if cursor == (x, y) => (),
+            // a short jump to the right within the same line
+            Some((cursor_x, cursor_y)) if cursor_y == y && cursor_x < x => {
+                let _ = write!(self.output, "\x1B[{}C", x - cursor_x);
+            }
+            _ => {
+                let _ = write!(self.output, "\x1B[{};{}H", y + 1, x + 1);
+            }
+        }
+    }
+
+    fn set_pen(&mut self, pen: Pen) {
+        if self.pen != Some(pen) {
+            pen.write_escape(&mut self.output);
+            self.pen = Some(pen);
+        }
+    }
+
+    fn set_background(&mut self, background: Option<Pen>) {
+        if self.background != Some(background) {
+            Pen::write_background_escape(background, &mut self.output);
+            self.background = Some(background);
+        }
+    }
+
+    /// Two cells are the same if they look the same in the terminal.
+    fn same_cell(&self, a: &Character, b: &Character) -> bool {
+        let pen_a = self.color_mode.pen(&a.color);
+        let pen_b = self.color_mode.pen(&b.color);
+        let background_a = self.color_mode.background_pen(a.background.as_ref());
+        let background_b = self.color_mode.background_pen(b.background.as_ref());
+
+        background_a == background_b
+            && pen_a == pen_b
+            && (a.character == b.character || !pen_a.visible())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::Color;
+
+    #[test]
+    fn test_diff() {
+        let mut renderer = Renderer::with_color_mode(ColorMode::TrueColor);
+        let mut frame = Frame::new(4, 2);
+
+        // the first frame is a full repaint
+        assert!(renderer.diff(&frame).len() >= 8);
+
+        // nothing changed
+        assert_eq!(renderer.diff(&frame), "");
+
+        frame.set_at(
+            1,
+            1,
+            Character {
+                character: 'a',
+                color: Color::white(),
+                background: None,
+            },
+        );
+        frame.set_at(
+            2,
+            1,
+            Character {
+                character: 'b',
+                color: Color::white(),
+                background: None,
+            },
+        );
+        assert_eq!(renderer.diff(&frame), "\x1B[2;2H\x1B[38;2;255;255;255mab");
+
+        // same color, short jump
+        frame.get_mut(0, 1).character = 'c';
+        frame.get_mut(3, 1).character = 'd';
+        frame.get_mut(0, 1).color = Color::white();
+        frame.get_mut(3, 1).color = Color::white();
+        assert_eq!(renderer.diff(&frame), "\x1B[2;1Hc\x1B[2Cd");
+
+        // a background color, then back to the default background
+        frame.get_mut(0, 0).background = Some(Color::white());
+        assert_eq!(
+            renderer.diff(&frame),
+            "\x1B[1;1H\x1B[38;2;0;0;0m\x1B[48;2;255;255;255m "
+        );
+        frame.get_mut(0, 0).background = None;
+        assert_eq!(renderer.diff(&frame), "\x1B[1;1H\x1B[49m ");
+    }
+
+    #[test]
+    fn test_background_palettes() {
+        let mut frame = Frame::new(1, 1);
+        frame.get_mut(0, 0).background = Some(Color::white());
+
+        let mut renderer = Renderer::with_color_mode(ColorMode::Ansi16);
+        assert!(renderer.diff(&frame).ends_with("\x1B[107m "));
+
+        // monochrome terminals keep the default background
+        let mut renderer = Renderer::with_color_mode(ColorMode::Monochrome);
+        assert!(!renderer.diff(&frame).contains("\x1B[48"));
+    }
+}
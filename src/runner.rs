@@ -0,0 +1,143 @@
+//! Drives an animation: calls the lifecycle hooks and keeps its clock.
+use crate::animations::Animation;
+use crate::clock::{Clock, Time};
+use crate::frame::{Frame, Size};
+use crate::input::Event;
+use std::time::Duration;
+
+pub struct Runner {
+    animation: Box<dyn Animation>,
+    clock: Clock,
+    /// the size of the last frame, `None` before the first frame
+    size: Option<Size>,
+    last_frame: Option<Duration>,
+}
+
+impl Runner {
+    pub fn new(animation: Box<dyn Animation>) -> Runner {
+        Runner {
+            animation,
+            clock: Clock::new(),
+            size: None,
+            last_frame: None,
+        }
+    }
+
+    pub fn animation(&self) -> &dyn Animation {
+        self.animation.as_ref()
+    }
+
+    pub fn animation_mut(&mut self) -> &mut dyn Animation {
+        self.animation.as_mut()
+    }
+
+    /// Renders the next frame.
+    ///
+    /// `now` is any monotonic time, only the differences between the calls matter. The first
+    /// frame is rendered with a delta of zero. `init()` is called before the first frame and
+    /// `resize()` whenever the size changes.
+    pub fn render(&mut self, size: Size, now: Duration) -> (Frame, Time) {
+        match self.size {
+            None => self.animation.init(size),
+            Some(old) if old != size => self.animation.resize(old, size),
+            Some(_) => (),
+        }
+        self.size = Some(size);
+
+        let delta = self
+            .last_frame
+            .map_or(Duration::ZERO, |last_frame| now.saturating_sub(last_frame));
+        self.last_frame = Some(now);
+        let time = self.clock.tick(delta);
+
+        let mut frame = Frame::new(size.width, size.height);
+        self.animation.render(&mut frame, &time);
+
+        (frame, time)
+    }
+
+    /// Passes the event on to the animation, events before the first frame are dropped.
+    pub fn handle_event(&mut self, event: &Event) {
+        if self.size.is_some() {
+            self.animation.handle_event(event);
+        }
+    }
+
+    /// Whether the animation asked to end early.
+    pub fn is_finished(&self) -> bool {
+        self.animation.is_finished()
+    }
+
+    /// Ends the animation, `finish()` is only called if a frame was rendered.
+    pub fn finish(mut self) {
+        if self.size.is_some() {
+            self.animation.finish();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// records the calls of the lifecycle hooks
+    struct Recorder(Rc<RefCell<Vec<String>>>);
+
+    impl Animation for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
+
+        fn author(&self) -> &'static str {
+            "test"
+        }
+
+        fn init(&mut self, size: Size) {
+            self.0
+                .borrow_mut()
+                .push(format!("init {}x{}", size.width, size.height));
+        }
+
+        fn resize(&mut self, old: Size, new: Size) {
+            self.0.borrow_mut().push(format!(
+                "resize {}x{} {}x{}",
+                old.width, old.height, new.width, new.height
+            ));
+        }
+
+        fn finish(&mut self) {
+            self.0.borrow_mut().push("finish".to_string());
+        }
+
+        fn render(&mut self, frame: &mut Frame, time: &Time) {
+            self.0
+                .borrow_mut()
+                .push(format!("render {} {}", frame.x, time.delta));
+        }
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let mut runner = Runner::new(Box::new(Recorder(calls.clone())));
+
+        runner.render(Size::new(10, 5), Duration::from_secs(3));
+        runner.render(Size::new(10, 5), Duration::from_secs(4));
+        runner.render(Size::new(20, 5), Duration::from_secs(6));
+        runner.finish();
+
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                "init 10x5",
+                "render 10 0",
+                "render 10 1",
+                "resize 10x5 20x5",
+                "render 20 2",
+                "finish"
+            ]
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Streams animations to telnet clients, so several screens can be fed from one machine.
+//!
+//! Every [`Client`] is a [`Sink`] for its frames and delivers the keys and mouse events of its
+//! viewer like [`crate::input::Input`] does for the local terminal. Frames are sent by a thread
+//! per client, so a viewer that does not keep up never holds up the show.
+use crate::color_mode::ColorMode;
+use crate::frame::{Frame, Size};
+use crate::input::{Event, Parser};
+use crate::renderer::Renderer;
+use crate::sink::Sink;
+use crate::telnet::{self, Decoder};
+use crate::terminal;
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// the size of clients that do not report their window size
+pub const DEFAULT_SIZE: Size = Size {
+    width: 80,
+    height: 24,
+};
+
+/// clients that do not take a frame within this time are disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// frames that wait for a slow client, further frames are dropped until it caught up
+const MAX_QUEUED: usize = 4;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// further clients are turned away
+    pub max_clients: usize,
+    /// clients that do not send anything for this long are disconnected
+    pub idle_timeout: Option<Duration>,
+    pub color_mode: ColorMode,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            max_clients: 8,
+            idle_timeout: None,
+            color_mode: ColorMode::TrueColor,
+        }
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    config: Config,
+    /// the number of connected clients
+    clients: Arc<AtomicUsize>,
+}
+
+impl Server {
+    pub fn bind(address: impl ToSocketAddrs, config: Config) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            config,
+            clients: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for the next client, clients over the limit are turned away.
+    pub fn accept(&self) -> io::Result<Client> {
+        loop {
+            let (stream, address) = self.listener.accept()?;
+            if let Some(client) = self.welcome(stream, address)? {
+                return Ok(client);
+            }
+        }
+    }
+
+    /// Returns a client that is waiting to connect, if there is one.
+    pub fn try_accept(&self) -> io::Result<Option<Client>> {
+        self.listener.set_nonblocking(true)?;
+        let accepted = self.listener.accept();
+        self.listener.set_nonblocking(false)?;
+
+        match accepted {
+            Ok((stream, address)) => self.welcome(stream, address),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Starts the negotiation or turns the client away if the server is full.
+    fn welcome(&self, mut stream: TcpStream, address: SocketAddr) -> io::Result<Option<Client>> {
+        stream.set_nonblocking(false)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+        let slot = Slot(self.clients.clone());
+        if self.clients.fetch_add(1, Ordering::SeqCst) >= self.config.max_clients {
+            drop(slot);
+            // the client is gone anyway if this fails
+            let _ = write!(
+                stream,
+                "Sorry, all {} places are taken, try again later.\r\n",
+                self.config.max_clients
+            );
+            let _ = stream.shutdown(Shutdown::Both);
+            return Ok(None);
+        }
+
+        stream.write_all(&telnet::NEGOTIATION)?;
+        let messages = Client::spawn_reader(stream.try_clone()?);
+
+        Ok(Some(Client {
+            outbox: Outbox::spawn(stream),
+            address,
+            messages,
+            size: DEFAULT_SIZE,
+            renderer: Renderer::with_color_mode(self.config.color_mode),
+            started: false,
+            closed: false,
+            last_input: Instant::now(),
+            idle_timeout: self.config.idle_timeout,
+            _slot: slot,
+        }))
+    }
+}
+
+/// Counts a connected client until it is dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+enum Message {
+    Event(Event),
+    Size(Size),
+    /// input that is not an event, it still counts against the idle timeout
+    Activity,
+}
+
+/// The output that waits to be sent to a client by its writer thread.
+struct Outbox {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+    /// set by the writer thread when a write failed or timed out
+    failed: AtomicBool,
+}
+
+#[derive(Default)]
+struct Queue {
+    outputs: VecDeque<Vec<u8>>,
+    /// no more output follows, the connection is closed once the queue is empty
+    done: bool,
+}
+
+impl Outbox {
+    /// Writes everything that is pushed in a background thread, which closes the connection
+    /// after the last output or when a write fails.
+    fn spawn(mut stream: TcpStream) -> Arc<Outbox> {
+        let outbox = Arc::new(Outbox {
+            queue: Mutex::new(Queue::default()),
+            changed: Condvar::new(),
+            failed: AtomicBool::new(false),
+        });
+
+        let writer = outbox.clone();
+        thread::spawn(move || {
+            while let Some(output) = writer.pop() {
+                if stream
+                    .write_all(&output)
+                    .and_then(|()| stream.flush())
+                    .is_err()
+                {
+                    writer.failed.store(true, Ordering::SeqCst);
+                    break;
+                }
+            }
+            // also ends the reader thread
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+
+        outbox
+    }
+
+    /// Waits for the next output, `None` once the outbox is closed and empty.
+    fn pop(&self) -> Option<Vec<u8>> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if let Some(output) = queue.outputs.pop_front() {
+                return Some(output);
+            }
+            if queue.done {
+                return None;
+            }
+            queue = self.changed.wait(queue).unwrap();
+        }
+    }
+
+    fn push(&self, output: Vec<u8>) {
+        self.queue.lock().unwrap().outputs.push_back(output);
+        self.changed.notify_one();
+    }
+
+    /// the number of outputs that were not sent yet
+    fn len(&self) -> usize {
+        self.queue.lock().unwrap().outputs.len()
+    }
+
+    /// Sends what is queued and closes the connection.
+    fn close(&self) {
+        self.queue.lock().unwrap().done = true;
+        self.changed.notify_one();
+    }
+}
+
+/// A connected viewer.
+pub struct Client {
+    outbox: Arc<Outbox>,
+    address: SocketAddr,
+    messages: Receiver<Message>,
+    size: Size,
+    renderer: Renderer,
+    /// whether the terminal of the client was prepared
+    started: bool,
+    closed: bool,
+    last_input: Instant,
+    idle_timeout: Option<Duration>,
+    _slot: Slot,
+}
+
+impl Client {
+    /// Reads the input in a background thread, it ends when the connection is closed.
+    fn spawn_reader(mut stream: TcpStream) -> Receiver<Message> {
+        let (sender, messages) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut decoder = Decoder::new();
+            let mut parser = Parser::new();
+            let mut buffer = [0; 256];
+
+            loop {
+                let length = match stream.read(&mut buffer) {
+                    Ok(0) | Err(_) => return,
+                    Ok(length) => length,
+                };
+
+                let (data, size) = decoder.decode(&buffer[..length]);
+                let messages = size
+                    .map(Message::Size)
+                    .into_iter()
+                    .chain(parser.parse(&data).into_iter().map(Message::Event))
+                    .chain(std::iter::once(Message::Activity));
+
+                for message in messages {
+                    if sender.send(message).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        messages
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The window size of the client, 80x24 until it reports one.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Whether the client disconnected, could not keep up or was idle for too long.
+    pub fn is_closed(&self) -> bool {
+        self.closed || self.outbox.failed.load(Ordering::SeqCst)
+    }
+
+    /// Disconnects the client after the output that is still queued.
+    pub fn close(&mut self) {
+        self.closed = true;
+        self.outbox.close();
+    }
+
+    /// Returns the next event if there is one.
+    pub fn poll(&mut self) -> Option<Event> {
+        loop {
+            match self.messages.try_recv() {
+                Ok(message) => {
+                    self.last_input = Instant::now();
+                    match message {
+                        Message::Event(event) => return Some(event),
+                        Message::Size(size) => self.size = size,
+                        Message::Activity => (),
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.closed = true;
+                    break;
+                }
+            }
+        }
+
+        if self
+            .idle_timeout
+            .is_some_and(|timeout| self.last_input.elapsed() > timeout)
+        {
+            self.closed = true;
+        }
+
+        None
+    }
+
+    /// Queues the bytes for the writer thread, it fails once the connection is lost.
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.is_closed() {
+            self.closed = true;
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "the client disconnected",
+            ));
+        }
+
+        self.outbox.push(bytes.to_vec());
+        Ok(())
+    }
+}
+
+impl Sink for Client {
+    fn present(&mut self, frame: &Frame, _time: Duration) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            self.write(terminal::ENTER.as_bytes())?;
+        }
+
+        // the client does not keep up, skip the frame and repaint everything once it caught up
+        if self.outbox.len() >= MAX_QUEUED {
+            self.renderer.invalidate();
+            return Ok(());
+        }
+
+        let output = self.renderer.diff(frame).to_string();
+        self.write(output.as_bytes())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.started && !self.closed {
+            self.write(terminal::LEAVE.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        self.renderer.bytes_per_frame()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        // the writer thread sends the rest and closes the connection, without holding up the show
+        self.outbox.close();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn connect(server: &Server) -> (TcpStream, Option<Client>) {
+        let stream = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        let client = server.accept().ok();
+        (stream, client)
+    }
+
+    #[test]
+    fn test_client() {
+        let server = Server::bind("127.0.0.1:0", Config::default()).unwrap();
+        let (mut stream, client) = connect(&server);
+        let mut client = client.unwrap();
+
+        let mut negotiation = [0; 9];
+        stream.read_exact(&mut negotiation).unwrap();
+        assert_eq!(negotiation, telnet::NEGOTIATION);
+
+        // a window size and a key
+        stream
+            .write_all(&[255, 250, 31, 0, 100, 0, 30, 255, 240, b'n'])
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let event = loop {
+            if let Some(event) = client.poll() {
+                break event;
+            }
+            assert!(Instant::now() < deadline, "no event arrived");
+            thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(event, Event::Key(crate::input::Key::Char('n')));
+        assert_eq!(client.size(), Size::new(100, 30));
+
+        client.present(&Frame::new(4, 2), Duration::ZERO).unwrap();
+        drop(client);
+
+        let mut output = Vec::new();
+        stream.read_to_end(&mut output).unwrap();
+        assert!(output.starts_with(terminal::ENTER.as_bytes()));
+    }
+
+    #[test]
+    fn test_slow_client() {
+        let server = Server::bind("127.0.0.1:0", Config::default()).unwrap();
+        // the viewer never reads, so the socket buffers fill up quickly
+        let (_stream, client) = connect(&server);
+        let mut client = client.unwrap();
+
+        let started = Instant::now();
+        let mut frames = [Frame::new(200, 100), Frame::new(200, 100)];
+        // alternating colors, so every cell of every frame needs its own escape sequence
+        for (index, character) in frames[1].data.iter_mut().enumerate() {
+            character.character = '#';
+            character.color.r = (index % 2) as f32;
+        }
+        for index in 0..200 {
+            client.present(&frames[index % 2], Duration::ZERO).unwrap();
+            assert!(client.outbox.len() <= MAX_QUEUED);
+        }
+
+        // every frame changes every cell, far more than the socket buffers hold
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!client.is_closed());
+    }
+
+    #[test]
+    fn test_max_clients() {
+        let config = Config {
+            max_clients: 1,
+            ..Config::default()
+        };
+        let server = Server::bind("127.0.0.1:0", config).unwrap();
+
+        let (_stream, first) = connect(&server);
+        assert!(first.is_some());
+
+        // the second client is turned away
+        let mut second = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        second
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(server.try_accept().unwrap().is_none());
+        let mut message = String::new();
+        second.read_to_string(&mut message).unwrap();
+        assert!(message.starts_with("Sorry"));
+
+        // there is room again after the first client left
+        drop(first);
+        let (_stream, third) = connect(&server);
+        assert!(third.is_some());
+    }
+}
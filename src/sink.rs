@@ -0,0 +1,217 @@
+//! Targets a rendered `Frame` can be presented on.
+//!
+//! The same animation loop can write to the terminal, keep the frames in memory or record them
+//! into an asciinema file, depending on the sink it is given.
+use crate::color_mode::ColorMode;
+use crate::frame::Frame;
+use crate::renderer::Renderer;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub trait Sink {
+    /// presents the frame. `time` is the time since the start of the output.
+    fn present(&mut self, frame: &Frame, time: Duration) -> io::Result<()>;
+
+    /// called once after the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// returns the number of bytes the last frame needed, 0 if the sink does not know.
+    fn bytes_per_frame(&self) -> usize {
+        0
+    }
+}
+
+/// Writes the frames to stdout.
+impl Sink for Renderer {
+    fn present(&mut self, frame: &Frame, _time: Duration) -> io::Result<()> {
+        self.render(frame)
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        Renderer::bytes_per_frame(self)
+    }
+}
+
+/// Keeps every presented frame in memory.
+#[derive(Default)]
+pub struct MemorySink {
+    pub frames: Vec<(Duration, Frame)>,
+}
+
+impl MemorySink {
+    pub fn new() -> MemorySink {
+        MemorySink::default()
+    }
+
+    /// returns the last presented frame
+    pub fn last(&self) -> Option<&Frame> {
+        self.frames.last().map(|(_, frame)| frame)
+    }
+}
+
+impl Sink for MemorySink {
+    fn present(&mut self, frame: &Frame, time: Duration) -> io::Result<()> {
+        self.frames.push((time, frame.clone()));
+        Ok(())
+    }
+}
+
+/// Records the frames as an asciicast v2 file that can be played back with asciinema.
+///
+/// See <https://docs.asciinema.org/manual/asciicast/v2/> for the format.
+pub struct AsciicastSink<W: Write> {
+    out: W,
+    renderer: Renderer,
+    /// written as `TERM` into the header
+    color_mode: ColorMode,
+    /// the size of the last frame, `None` until the header is written.
+    size: Option<(usize, usize)>,
+}
+
+impl<W: Write> AsciicastSink<W> {
+    pub fn new(out: W) -> AsciicastSink<W> {
+        AsciicastSink::with_color_mode(out, ColorMode::TrueColor)
+    }
+
+    pub fn with_color_mode(out: W, color_mode: ColorMode) -> AsciicastSink<W> {
+        AsciicastSink {
+            out,
+            renderer: Renderer::with_color_mode(color_mode),
+            color_mode,
+            size: None,
+        }
+    }
+
+    fn write_header(&mut self, frame: &Frame) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0);
+
+        writeln!(
+            self.out,
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"env\": {{\"TERM\": \"{}\"}}}}",
+            frame.x,
+            frame.y,
+            timestamp,
+            self.color_mode.term()
+        )
+    }
+
+    fn write_event(&mut self, time: Duration, code: &str, data: &str) -> io::Result<()> {
+        writeln!(
+            self.out,
+            "[{:.6}, \"{}\", \"{}\"]",
+            time.as_secs_f64(),
+            code,
+            json_escape(data)
+        )
+    }
+}
+
+impl<W: Write> Sink for AsciicastSink<W> {
+    fn present(&mut self, frame: &Frame, time: Duration) -> io::Result<()> {
+        match self.size {
+            None => self.write_header(frame)?,
+            Some(size) if size != (frame.x, frame.y) => {
+                self.write_event(time, "r", &format!("{}x{}", frame.x, frame.y))?;
+            }
+            Some(_) => (),
+        }
+        self.size = Some((frame.x, frame.y));
+
+        let output = self.renderer.diff(frame).to_string();
+
+        if !output.is_empty() {
+            self.write_event(time, "o", &output)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    fn bytes_per_frame(&self) -> usize {
+        self.renderer.bytes_per_frame()
+    }
+}
+
+/// escapes a string to be used inside of a JSON string literal.
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        match character {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            '\r' => escaped += "\\r",
+            '\t' => escaped += "\\t",
+            c if (c as u32) < 0x20 || c == '\x7f' => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_escape() {
+        assert_eq!(json_escape("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+        assert_eq!(json_escape("\x1B[1;1H"), "\\u001b[1;1H");
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let mut sink = AsciicastSink::new(Vec::new());
+        let frame = Frame::new(3, 2);
+
+        sink.present(&frame, Duration::ZERO).unwrap();
+        // an unchanged frame does not produce an event
+        sink.present(&frame, Duration::from_millis(16)).unwrap();
+        sink.present(&Frame::new(4, 2), Duration::from_millis(32))
+            .unwrap();
+        sink.finish().unwrap();
+
+        let output = String::from_utf8(sink.out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 3, \"height\": 2,"));
+        assert!(lines[1].starts_with("[0.000000, \"o\", \"\\u001b[?25l"));
+        assert_eq!(lines[2], "[0.032000, \"r\", \"4x2\"]");
+        assert!(lines[3].starts_with("[0.032000, \"o\", "));
+    }
+
+    #[test]
+    fn test_asciicast_term() {
+        for color_mode in [
+            ColorMode::TrueColor,
+            ColorMode::Ansi16,
+            ColorMode::Monochrome,
+        ] {
+            let mut sink = AsciicastSink::with_color_mode(Vec::new(), color_mode);
+            sink.present(&Frame::new(3, 2), Duration::ZERO).unwrap();
+
+            let output = String::from_utf8(sink.out).unwrap();
+            let header = output.lines().next().unwrap();
+            let term = format!("\"env\": {{\"TERM\": \"{}\"}}", color_mode.term());
+            assert!(header.contains(&term), "{}", header);
+            assert_eq!(
+                ColorMode::from_env(None, Some(color_mode.term())),
+                color_mode
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! The parts of the telnet protocol that are needed to stream animations.
+//!
+//! Clients are asked to switch to character mode and to report their window size
+//! ([RFC 1073](https://www.rfc-editor.org/rfc/rfc1073), NAWS). Everything else they negotiate
+//! is ignored.
+use crate::frame::Size;
+
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const ECHO: u8 = 1;
+const SUPPRESS_GO_AHEAD: u8 = 3;
+const NAWS: u8 = 31;
+
+/// Sent when a client connects: the server echoes, so the client stops echoing and sends every
+/// key immediately, and the client should report its window size.
+pub const NEGOTIATION: [u8; 9] = [IAC, WILL, ECHO, IAC, WILL, SUPPRESS_GO_AHEAD, IAC, DO, NAWS];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Data,
+    /// after a carriage return, which may be followed by a NUL or line feed
+    Return,
+    Iac,
+    /// after WILL, WONT, DO or DONT, the next byte is the option
+    Option,
+    Subnegotiation,
+    SubnegotiationIac,
+}
+
+/// Separates the input of the user from telnet commands.
+#[derive(Debug)]
+pub struct Decoder {
+    state: State,
+    subnegotiation: Vec<u8>,
+}
+
+impl Default for Decoder {
+    fn default() -> Decoder {
+        Decoder {
+            state: State::Data,
+            subnegotiation: Vec::new(),
+        }
+    }
+}
+
+impl Decoder {
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+
+    /// Returns the input bytes and the last window size that was reported, if any.
+    ///
+    /// Commands that are split between two reads are completed by the next call.
+    pub fn decode(&mut self, bytes: &[u8]) -> (Vec<u8>, Option<Size>) {
+        let mut data = Vec::with_capacity(bytes.len());
+        let mut size = None;
+
+        for &byte in bytes {
+            self.state = match (self.state, byte) {
+                (State::Data | State::Return, IAC) => State::Iac,
+                // Enter is sent as CR NUL or CR LF
+                (State::Return, 0 | b'\n') => State::Data,
+                (State::Data | State::Return, byte) => {
+                    data.push(byte);
+                    if byte == b'\r' {
+                        State::Return
+                    } else {
+                        State::Data
+                    }
+                }
+                (State::Iac, IAC) => {
+                    data.push(IAC);
+                    State::Data
+                }
+                (State::Iac, WILL | WONT | DO | DONT) => State::Option,
+                (State::Iac, SB) => {
+                    self.subnegotiation.clear();
+                    State::Subnegotiation
+                }
+                // any other command has no arguments
+                (State::Iac, _) | (State::Option, _) => State::Data,
+                (State::Subnegotiation, IAC) => State::SubnegotiationIac,
+                (State::Subnegotiation, byte) | (State::SubnegotiationIac, byte @ IAC) => {
+                    self.subnegotiation.push(byte);
+                    State::Subnegotiation
+                }
+                (State::SubnegotiationIac, SE) => {
+                    size = window_size(&self.subnegotiation).or(size);
+                    State::Data
+                }
+                // a broken subnegotiation, start over
+                (State::SubnegotiationIac, _) => State::Data,
+            };
+        }
+
+        (data, size)
+    }
+}
+
+/// Reads the window size from a NAWS subnegotiation, sizes of zero are not known to the client.
+fn window_size(subnegotiation: &[u8]) -> Option<Size> {
+    let &[NAWS, width_high, width_low, height_high, height_low] = subnegotiation else {
+        return None;
+    };
+
+    let width = u16::from_be_bytes([width_high, width_low]) as usize;
+    let height = u16::from_be_bytes([height_high, height_low]) as usize;
+
+    (width > 0 && height > 0).then_some(Size::new(width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let mut decoder = Decoder::new();
+
+        // answers to the negotiation and a window size of 300x40
+        let (data, size) = decoder.decode(&[
+            IAC, DO, ECHO, b'a', IAC, WILL, NAWS, IAC, SB, NAWS, 1, 44, 0, 40, IAC, SE, b'b',
+        ]);
+        assert_eq!(data, b"ab");
+        assert_eq!(size, Some(Size::new(300, 40)));
+
+        // a size that is split between two reads and contains an escaped 255
+        assert_eq!(decoder.decode(&[IAC, SB, NAWS, 0]), (vec![], None));
+        assert_eq!(
+            decoder.decode(&[IAC, IAC, 0, 24, IAC, SE]),
+            (vec![], Some(Size::new(255, 24)))
+        );
+
+        // Enter, an escaped 255 and a broken window size
+        assert_eq!(
+            decoder.decode(&[b'\r', 0, b'\r', b'\n', IAC, IAC, IAC, SB, NAWS, 0, IAC, SE]),
+            (vec![b'\r', b'\r', IAC], None)
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Puts the terminal into a state suitable for animations and restores it afterwards.
+//!
+//! A [`Session`] switches to the alternate screen, hides the cursor, disables line wrapping,
+//! enables mouse reporting and stops the terminal from echoing input. Everything is restored when
+//! the session is dropped, when the program panics and, if the binary calls [`restore`] from its
+//! signal handler, on SIGINT, SIGTERM and SIGHUP.
+use crate::frame::Size;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Mutex, Once};
+
+/// switch to the alternate screen, hide the cursor, disable line wrapping, clear the screen and
+/// report mouse clicks and drags as SGR sequences
+pub(crate) const ENTER: &str =
+    "\x1B[?1049h\x1B[?25l\x1B[?7l\x1B[2J\x1B[?1000h\x1B[?1002h\x1B[?1006h";
+/// stop the mouse reports, reset the colors, enable line wrapping, show the cursor and leave the
+/// alternate screen
+pub(crate) const LEAVE: &str =
+    "\x1B[?1006l\x1B[?1002l\x1B[?1000l\x1B[0m\x1B[?7h\x1B[?25h\x1B[?1049l";
+
+/// The state that has to be restored, `None` if no session is active.
+static ACTIVE: Mutex<Option<Saved>> = Mutex::new(None);
+
+struct Saved {
+    /// the terminal attributes before the session, `None` if stdin is not a terminal
+    #[cfg(unix)]
+    attributes: Option<libc::termios>,
+}
+
+pub struct Session {
+    // only created by `start()`
+    _private: (),
+}
+
+impl Session {
+    /// Prepares the terminal for animations.
+    ///
+    /// Input is not echoed and not line buffered anymore, but Ctrl+C still sends SIGINT.
+    pub fn start() -> io::Result<Session> {
+        let mut active = ACTIVE.lock().unwrap_or_else(|error| error.into_inner());
+        if active.is_some() {
+            return Err(io::Error::other("a terminal session is already active"));
+        }
+
+        let saved = Saved {
+            #[cfg(unix)]
+            attributes: disable_echo()?,
+        };
+        *active = Some(saved);
+        drop(active);
+
+        install_panic_hook();
+
+        let mut stdout = io::stdout();
+        stdout.write_all(ENTER.as_bytes())?;
+        stdout.flush()?;
+
+        Ok(Session { _private: () })
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Restores the terminal to the state before the session started.
+///
+/// This does nothing if no session is active, so it is safe to call it from signal handlers
+/// and panic hooks even if the session was already dropped.
+pub fn restore() {
+    let saved = ACTIVE
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .take();
+
+    let Some(saved) = saved else {
+        return;
+    };
+
+    #[cfg(unix)]
+    if let Some(attributes) = saved.attributes {
+        // SAFETY: the attributes were read from the same file descriptor by `disable_echo()`
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &attributes);
+        }
+    }
+
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(LEAVE.as_bytes());
+    let _ = stdout.flush();
+}
+
+/// The size of the terminal in characters, 80x24 if it is not known, e.g. when the output is
+/// redirected.
+pub fn size() -> Size {
+    terminal_size::terminal_size()
+        .map(|(width, height)| Size::new(width.0 as usize, height.0 as usize))
+        .unwrap_or(Size::new(80, 24))
+}
+
+/// Restores the terminal before the panic message is printed, so it is not lost on the
+/// alternate screen.
+fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore();
+            previous(info);
+        }));
+    });
+}
+
+/// Turns off echo and line buffering of stdin and returns the previous attributes.
+#[cfg(unix)]
+fn disable_echo() -> io::Result<Option<libc::termios>> {
+    if !io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    // SAFETY: termios is a plain C struct that is filled by tcgetattr
+    unsafe {
+        let mut attributes: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(libc::STDIN_FILENO, &mut attributes) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let original = attributes;
+        // ISIG stays enabled, so Ctrl+C still works
+        attributes.c_lflag &= !(libc::ECHO | libc::ICANON);
+        attributes.c_cc[libc::VMIN] = 1;
+        attributes.c_cc[libc::VTIME] = 0;
+
+        if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &attributes) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Some(original))
+    }
+}
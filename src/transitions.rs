@@ -0,0 +1,284 @@
+//! Transitions between two animations.
+//!
+//! During a transition both the outgoing and the incoming animation are rendered into frames of
+//! the same size, which are then blended depending on the progress of the transition.
+use crate::filters::fadeout;
+use crate::frame::{Character, Color, Frame};
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// fade the outgoing animation to black, then fade in the incoming one
+    Fade,
+    /// blend the colors of both animations
+    Crossfade,
+    /// the incoming animation pushes in from the left
+    WipeHorizontal,
+    /// the incoming animation pushes in from the top
+    WipeVertical,
+    /// random cells switch to the incoming animation
+    Dissolve,
+    /// falling green characters uncover the incoming animation
+    MatrixRain,
+}
+
+impl Transition {
+    pub const ALL: [Transition; 6] = [
+        Transition::Fade,
+        Transition::Crossfade,
+        Transition::WipeHorizontal,
+        Transition::WipeVertical,
+        Transition::Dissolve,
+        Transition::MatrixRain,
+    ];
+
+    /// Picks one of the transitions at random.
+    pub fn random(rng: &mut impl Rng) -> Transition {
+        Transition::ALL[rng.gen_range(0..Transition::ALL.len())]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transition::Fade => "fade",
+            Transition::Crossfade => "crossfade",
+            Transition::WipeHorizontal => "wipe-horizontal",
+            Transition::WipeVertical => "wipe-vertical",
+            Transition::Dissolve => "dissolve",
+            Transition::MatrixRain => "matrix-rain",
+        }
+    }
+
+    /// Blends the incoming frame into the outgoing one.
+    ///
+    /// At a progress of 0.0 the outgoing frame is unchanged, at 1.0 it is replaced by the
+    /// incoming frame. Both frames have to be of the same size.
+    pub fn apply(&self, outgoing: &mut Frame, incoming: &Frame, progress: f32) {
+        debug_assert_eq!((outgoing.x, outgoing.y), (incoming.x, incoming.y));
+        let progress = progress.clamp(0.0, 1.0);
+
+        match self {
+            Transition::Fade => {
+                if progress < 0.5 {
+                    fadeout(outgoing, 1.0 - 2.0 * progress);
+                } else {
+                    outgoing.data.clone_from(&incoming.data);
+                    fadeout(outgoing, 2.0 * progress - 1.0);
+                }
+            }
+            Transition::Crossfade => {
+                for (from, to) in outgoing.data.iter_mut().zip(incoming.data.iter()) {
+                    // the character switches halfway, blank cells show the incoming one right away
+                    if from.character == ' ' || progress >= 0.5 {
+                        from.character = to.character;
+                    }
+                    from.color = from.color.interpolate(&to.color, progress);
+                    // a missing background blends like black and is missing again at the end
+                    if progress >= 1.0 {
+                        from.background.clone_from(&to.background);
+                    } else if from.background.is_some() || to.background.is_some() {
+                        let below = from.background.take().unwrap_or(Color::BLACK);
+                        let above = to.background.as_ref().unwrap_or(&Color::BLACK);
+                        from.background = Some(below.interpolate(above, progress));
+                    }
+                }
+            }
+            Transition::WipeHorizontal => {
+                let edge = (progress * outgoing.x as f32).round() as usize;
+                for y in 0..outgoing.y {
+                    let row = y * outgoing.x;
+                    outgoing.data[row..row + edge]
+                        .clone_from_slice(&incoming.data[row..row + edge]);
+                }
+            }
+            Transition::WipeVertical => {
+                let edge = (progress * outgoing.y as f32).round() as usize * outgoing.x;
+                outgoing.data[..edge].clone_from_slice(&incoming.data[..edge]);
+            }
+            Transition::Dissolve => {
+                for (index, (from, to)) in outgoing
+                    .data
+                    .iter_mut()
+                    .zip(incoming.data.iter())
+                    .enumerate()
+                {
+                    if noise(index, 0) < progress {
+                        *from = to.clone();
+                    }
+                }
+            }
+            Transition::MatrixRain => matrix_rain(outgoing, incoming, progress),
+        }
+    }
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Transition {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Transition, String> {
+        Transition::ALL
+            .into_iter()
+            .find(|transition| transition.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Transition::ALL.iter().map(|t| t.name()).collect();
+                format!(
+                    "unknown transition '{}', use one of {}",
+                    text,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Length of the trail of the falling characters in cells.
+const RAIN_TRAIL: usize = 8;
+const RAIN_COLOR: Color = Color {
+    r: 0.0,
+    g: 1.0,
+    b: 0.0,
+};
+
+/// Every column gets a drop that falls at its own speed, everything above its trail shows the
+/// incoming frame.
+fn matrix_rain(outgoing: &mut Frame, incoming: &Frame, progress: f32) {
+    const CHARACTERS: &[u8] =
+        b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789@#$%&*+=";
+    let distance = (outgoing.y + RAIN_TRAIL + 1) as f32;
+
+    for x in 0..outgoing.x {
+        // the slowest drops leave the bottom just when the transition ends
+        let speed = 1.0 + noise(x, 1);
+        let fallen = (progress * speed * distance) as usize;
+
+        for y in 0..outgoing.y.min(fallen) {
+            let index = y * outgoing.x + x;
+            // distance to the head of the drop
+            let behind = fallen - 1 - y;
+
+            if behind > RAIN_TRAIL {
+                outgoing.data[index] = incoming.data[index].clone();
+                continue;
+            }
+
+            let brightness = 1.0 - behind as f32 / RAIN_TRAIL as f32;
+            let color = if behind == 0 {
+                Color::WHITE
+            } else {
+                Color::BLACK.interpolate(&RAIN_COLOR, brightness)
+            };
+            let choice = (noise(index, fallen) * CHARACTERS.len() as f32) as usize;
+
+            outgoing.data[index] = Character {
+                color,
+                character: CHARACTERS[choice.min(CHARACTERS.len() - 1)] as char,
+                background: None,
+            };
+        }
+    }
+}
+
+/// A stable pseudo random value between 0.0 and 1.0 for the given pair of numbers.
+fn noise(a: usize, b: usize) -> f32 {
+    // splitmix64
+    let mut z = (a as u64)
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(b as u64)
+        .wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+
+    (z >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn filled(character: char, color: Color) -> Frame {
+        let mut frame = Frame::new(8, 4);
+        for cell in frame.data.iter_mut() {
+            *cell = Character {
+                color: color.clone(),
+                character,
+                background: None,
+            };
+        }
+        frame
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let from = filled('a', Color::WHITE);
+        let to = filled('b', RAIN_COLOR);
+
+        for transition in Transition::ALL {
+            let mut frame = from.clone();
+            transition.apply(&mut frame, &to, 0.0);
+            assert!(
+                frame.data.iter().all(|cell| cell.character == 'a'),
+                "{} changed the frame at the start",
+                transition
+            );
+
+            let mut frame = from.clone();
+            transition.apply(&mut frame, &to, 1.0);
+            assert!(
+                frame
+                    .data
+                    .iter()
+                    .all(|cell| cell.character == 'b' && cell.color == RAIN_COLOR),
+                "{} did not finish at the end",
+                transition
+            );
+        }
+    }
+
+    #[test]
+    fn test_crossfade_to_blank() {
+        let from = filled('a', Color::WHITE);
+        let to = filled(' ', Color::BLACK);
+
+        let mut frame = from.clone();
+        Transition::Crossfade.apply(&mut frame, &to, 0.25);
+        assert_eq!(frame.get(0, 0).character, 'a');
+
+        let mut frame = from.clone();
+        frame.get_mut(0, 0).background = Some(Color::WHITE);
+        Transition::Crossfade.apply(&mut frame, &to, 1.0);
+        assert!(frame
+            .data
+            .iter()
+            .zip(to.data.iter())
+            .all(|(cell, incoming)| {
+                cell.character == incoming.character
+                    && cell.color == incoming.color
+                    && cell.background == incoming.background
+            }));
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut frame = filled('a', Color::WHITE);
+        Transition::WipeHorizontal.apply(&mut frame, &filled('b', Color::WHITE), 0.5);
+
+        let row: String = frame.data[..8].iter().map(|cell| cell.character).collect();
+        assert_eq!(row, "bbbbaaaa");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("Crossfade".parse(), Ok(Transition::Crossfade));
+        for transition in Transition::ALL {
+            assert_eq!(transition.to_string().parse(), Ok(transition));
+        }
+        assert!("cut".parse::<Transition>().is_err());
+    }
+}
@@ -1,8 +1,9 @@
-use crate::frame::{Character, Frame};
+use crate::canvas::Surface;
+use crate::frame::Character;
 use nalgebra::Vector2;
 
 pub fn fill_block(
-    frame: &mut Frame,
+    frame: &mut (impl Surface + ?Sized),
     x: isize,
     y: isize,
     width: usize,
@@ -18,12 +19,12 @@ pub fn fill_block(
     }
 }
 
-/// Samples the function on a grid given by the frame, scaled to the
+/// Samples the function on a grid given by the frame or canvas, scaled to the
 /// given x/y min/max values.
 ///
 /// The paramters of f are x, y and a time coordinate.
 pub fn sample(
-    frame: &mut Frame,
+    frame: &mut (impl Surface + ?Sized),
     x_min: f32,
     x_max: f32,
     y_min: f32,
@@ -33,11 +34,12 @@ pub fn sample(
 ) {
     let width = x_max - x_min;
     let height = y_max - y_min;
+    let size = frame.size();
 
-    for x in 0..frame.x {
-        let x_query = x_min + (x as f32 / frame.x as f32 * width);
-        for y in 0..frame.y {
-            let y_query = y_min + (y as f32 / frame.y as f32 * height);
+    for x in 0..size.width {
+        let x_query = x_min + (x as f32 / size.width as f32 * width);
+        for y in 0..size.height {
+            let y_query = y_min + (y as f32 / size.height as f32 * height);
 
             frame.set_at_clipping(x as isize, y as isize, f(x_query, y_query, time))
         }
@@ -119,7 +121,7 @@ pub fn clip(
 
 /// plots the line for lines with slope between [-1, 1].
 pub fn plot_line_low(
-    frame: &mut Frame,
+    frame: &mut (impl Surface + ?Sized),
     x0: isize,
     y0: isize,
     x1: isize,
@@ -152,7 +154,7 @@ pub fn plot_line_low(
 
 /// plots the line for lines with slope between [-1, 1].
 pub fn plot_line_high(
-    frame: &mut Frame,
+    frame: &mut (impl Surface + ?Sized),
     x0: isize,
     y0: isize,
     x1: isize,
@@ -184,7 +186,7 @@ pub fn plot_line_high(
 }
 
 pub fn plot_line(
-    frame: &mut Frame,
+    frame: &mut (impl Surface + ?Sized),
     start: Vector2<f32>,
     end: Vector2<f32>,
     character: &dyn Fn(isize, isize) -> Character,
@@ -0,0 +1,286 @@
+//! Rectangles of a frame that can be drawn into like a frame of their own.
+//!
+//! A [`Viewport`] has its own coordinate system with 0,0 in the top left corner of its
+//! rectangle, so animations, `Frame::set_at_clipping` and `utils::sample` work inside of it
+//! without changes. A [`Layout`] splits the screen into such rectangles.
+use crate::canvas::Surface;
+use crate::frame::{Character, Frame, Size};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// A rectangle of cells, `x` and `y` are its top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// The part of the rectangle that lies within the given size.
+    pub fn clip(&self, size: Size) -> Rect {
+        let x = self.x.min(size.width);
+        let y = self.y.min(size.height);
+
+        Rect {
+            x,
+            y,
+            width: self.width.min(size.width - x),
+            height: self.height.min(size.height - y),
+        }
+    }
+}
+
+/// Borrows a rectangle of a frame.
+///
+/// The viewport dereferences to a frame of the size of the rectangle, which is copied back into
+/// the parent frame when the viewport is dropped. Everything drawn outside of the rectangle is
+/// clipped.
+pub struct Viewport<'a> {
+    parent: &'a mut Frame,
+    rect: Rect,
+    frame: Frame,
+}
+
+impl<'a> Viewport<'a> {
+    /// The rectangle is clipped to the parent frame.
+    pub fn new(parent: &'a mut Frame, rect: Rect) -> Viewport<'a> {
+        let rect = rect.clip(parent.size());
+
+        let mut frame = Frame::new(rect.width, rect.height);
+        for y in 0..rect.height {
+            let row = (rect.y + y) * parent.x + rect.x;
+            frame.data[y * rect.width..(y + 1) * rect.width]
+                .clone_from_slice(&parent.data[row..row + rect.width]);
+        }
+
+        Viewport {
+            parent,
+            rect,
+            frame,
+        }
+    }
+
+    /// The rectangle of the parent frame, after clipping.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+}
+
+impl Deref for Viewport<'_> {
+    type Target = Frame;
+
+    fn deref(&self) -> &Frame {
+        &self.frame
+    }
+}
+
+impl DerefMut for Viewport<'_> {
+    fn deref_mut(&mut self) -> &mut Frame {
+        &mut self.frame
+    }
+}
+
+impl Surface for Viewport<'_> {
+    fn size(&self) -> Size {
+        self.frame.size()
+    }
+
+    fn set_at_clipping(&mut self, x: isize, y: isize, character: Character) {
+        self.frame.set_at_clipping(x, y, character);
+    }
+}
+
+impl Drop for Viewport<'_> {
+    fn drop(&mut self) {
+        // the frame may have been replaced by one of another size
+        let width = self.rect.width.min(self.frame.x);
+        let height = self.rect.height.min(self.frame.y);
+
+        for y in 0..height {
+            let row = (self.rect.y + y) * self.parent.x + self.rect.x;
+            self.parent.data[row..row + width]
+                .clone_from_slice(&self.frame.data[y * self.frame.x..y * self.frame.x + width]);
+        }
+    }
+}
+
+/// How the screen is split between several animations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Layout {
+    /// one animation on the whole screen
+    #[default]
+    Single,
+    /// two animations side by side
+    Columns,
+    /// two animations above each other
+    Rows,
+    /// four animations in a 2x2 grid
+    Grid,
+    /// one animation with a ticker line at the bottom
+    Ticker,
+}
+
+impl Layout {
+    pub const ALL: [Layout; 5] = [
+        Layout::Single,
+        Layout::Columns,
+        Layout::Rows,
+        Layout::Grid,
+        Layout::Ticker,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layout::Single => "single",
+            Layout::Columns => "columns",
+            Layout::Rows => "rows",
+            Layout::Grid => "grid",
+            Layout::Ticker => "ticker",
+        }
+    }
+
+    /// The number of animations that are shown at once.
+    pub fn tile_count(&self) -> usize {
+        match self {
+            Layout::Single | Layout::Ticker => 1,
+            Layout::Columns | Layout::Rows => 2,
+            Layout::Grid => 4,
+        }
+    }
+
+    /// The rectangles of the animations, always `tile_count()` of them.
+    ///
+    /// Tiles that do not fit on a tiny screen are empty.
+    pub fn tiles(&self, size: Size) -> Vec<Rect> {
+        let Size { width, height } = size;
+        let left = width / 2;
+        let top = height / 2;
+
+        match self {
+            Layout::Single => vec![Rect::new(0, 0, width, height)],
+            Layout::Columns => vec![
+                Rect::new(0, 0, left, height),
+                Rect::new(left, 0, width - left, height),
+            ],
+            Layout::Rows => vec![
+                Rect::new(0, 0, width, top),
+                Rect::new(0, top, width, height - top),
+            ],
+            Layout::Grid => vec![
+                Rect::new(0, 0, left, top),
+                Rect::new(left, 0, width - left, top),
+                Rect::new(0, top, left, height - top),
+                Rect::new(left, top, width - left, height - top),
+            ],
+            Layout::Ticker => vec![Rect::new(0, 0, width, height.saturating_sub(1))],
+        }
+    }
+
+    /// The line for the ticker, if the layout has one.
+    pub fn ticker(&self, size: Size) -> Option<Rect> {
+        match self {
+            Layout::Ticker if size.height > 0 => Some(Rect::new(0, size.height - 1, size.width, 1)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Layout, String> {
+        Layout::ALL
+            .into_iter()
+            .find(|layout| layout.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Layout::ALL.iter().map(|l| l.name()).collect();
+                format!("unknown layout '{}', use one of {}", text, names.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::frame::{Character, Color};
+
+    fn text(frame: &Frame) -> String {
+        frame
+            .data
+            .chunks(frame.x)
+            .map(|row| row.iter().map(|cell| cell.character).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut frame = Frame::new(5, 3);
+        let x = Character {
+            color: Color::WHITE,
+            character: 'x',
+            background: None,
+        };
+
+        {
+            let mut viewport = Viewport::new(&mut frame, Rect::new(1, 1, 3, 5));
+            assert_eq!(viewport.size(), Size::new(3, 2));
+
+            // local coordinates, clipped at the edge of the viewport
+            for position in [(0, 0), (2, 1), (3, 1), (-1, 0), (1, 2)] {
+                viewport.set_at_clipping(position.0, position.1, x.clone());
+            }
+        }
+
+        assert_eq!(text(&frame), "     \n x   \n   x ");
+    }
+
+    #[test]
+    fn test_tiles() {
+        let size = Size::new(81, 25);
+
+        for layout in Layout::ALL {
+            let tiles = layout.tiles(size);
+            assert_eq!(tiles.len(), layout.tile_count());
+
+            // the tiles and the ticker cover every cell exactly once
+            let mut covered = vec![0; size.width * size.height];
+            for rect in tiles.iter().chain(layout.ticker(size).iter()) {
+                for y in rect.y..rect.y + rect.height {
+                    for x in rect.x..rect.x + rect.width {
+                        covered[y * size.width + x] += 1;
+                    }
+                }
+            }
+            assert!(covered.iter().all(|&count| count == 1), "{}", layout);
+        }
+
+        assert_eq!("Grid".parse(), Ok(Layout::Grid));
+    }
+}
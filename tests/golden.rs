@@ -0,0 +1,136 @@
+//! Golden-frame tests for all animations in the registry.
+//!
+//! Every animation is rendered with a fixed seed, frame size and frame rate. The last frame is
+//! compared against the snapshot in `tests/snapshots/<id>.txt`.
+//!
+//! After an intended change of the output, the snapshots are updated with
+//! `BLESS=1 cargo test --test golden`.
+use asciimation::animations::{Entry, REGISTRY};
+use asciimation::frame::{Frame, Size};
+use asciimation::runner::Runner;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const WIDTH: usize = 48;
+const HEIGHT: usize = 24;
+const FRAMES: usize = 30;
+const SEED: u64 = 1;
+const STEP: Duration = Duration::from_millis(50);
+
+/// Renders the animation and returns its last frame.
+fn render(entry: &Entry) -> Frame {
+    let mut runner = Runner::new(entry.create_seeded(SEED));
+    let mut frame = Frame::new(0, 0);
+
+    for index in 0..FRAMES {
+        (frame, _) = runner.render(Size::new(WIDTH, HEIGHT), STEP * index as u32);
+    }
+
+    frame
+}
+
+/// Writes the characters of the frame followed by their colors.
+///
+/// Every color is quantized to one hex digit per channel, so small rounding differences
+/// do not matter.
+fn snapshot(entry: &Entry, frame: &Frame) -> String {
+    let mut text = format!(
+        "{} {}x{} seed {} frame {}\n",
+        entry.id,
+        frame.x,
+        frame.y,
+        SEED,
+        FRAMES - 1
+    );
+
+    for row in frame.data.chunks(frame.x) {
+        text.extend(row.iter().map(|cell| cell.character));
+        text.push('\n');
+    }
+
+    text.push_str("--\n");
+
+    for row in frame.data.chunks(frame.x) {
+        for cell in row {
+            let quantize = |value: f32| (value.clamp(0.0, 1.0) * 15.0).round() as u8;
+            write!(
+                text,
+                "{:x}{:x}{:x} ",
+                quantize(cell.color.r),
+                quantize(cell.color.g),
+                quantize(cell.color.b)
+            )
+            .unwrap();
+        }
+        text.pop();
+        text.push('\n');
+    }
+
+    text
+}
+
+fn snapshot_path(entry: &Entry) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", entry.id))
+}
+
+/// Returns the first line that differs, for a readable failure message.
+fn first_difference(expected: &str, actual: &str) -> String {
+    for (number, (expected, actual)) in expected.lines().zip(actual.lines()).enumerate() {
+        if expected != actual {
+            return format!(
+                "line {}:\n  expected: {}\n  actual:   {}",
+                number + 1,
+                expected,
+                actual
+            );
+        }
+    }
+
+    format!(
+        "expected {} lines, got {}",
+        expected.lines().count(),
+        actual.lines().count()
+    )
+}
+
+#[test]
+fn test_golden_frames() {
+    let bless = std::env::var_os("BLESS").is_some_and(|value| value != "0");
+    let mut failures = Vec::new();
+
+    for entry in REGISTRY.iter() {
+        let actual = snapshot(entry, &render(entry));
+        let path = snapshot_path(entry);
+
+        if bless {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => (),
+            Ok(expected) => failures.push(format!(
+                "{} does not match its snapshot, {}",
+                entry.id,
+                first_difference(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!(
+                "{} has no snapshot at {}",
+                entry.id,
+                path.display()
+            )),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\n\nRun `BLESS=1 cargo test --test golden` if the changes are intended.",
+        failures.join("\n")
+    );
+}